@@ -1,16 +1,30 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use serde::{Deserialize, Serialize};
 
+pub mod baseline;
 pub mod general;
 pub mod name_params;
+pub mod profile;
+pub mod recovery;
+pub mod sample_conversion;
 pub mod sample_naming;
+pub mod sample_processing;
 pub mod sample_ripping;
 // pub mod filters;
 
+pub use baseline::Baseline;
 pub use general::GeneralConfig;
 pub use name_params::SampleNameParams;
+pub use profile::Profile;
+pub use recovery::{Recovery, VERSION};
+pub use sample_conversion::SampleConversionConfig;
 pub use sample_naming::SampleNameConfig;
+pub use sample_processing::SampleProcessingConfig;
 pub use sample_ripping::SampleRippingConfig;
 
 use anyhow::Result;
@@ -19,6 +33,8 @@ use tracing::{error, info, warn};
 
 const APP_NAME: &str = "xmodits";
 const CONFIG_NAME: &str = "config.toml";
+/// Backups are named "config.backup.<unix time>.toml", so older ones aren't overwritten.
+const BACKUP_PREFIX: &str = "config.backup";
 
 pub fn config_dir() -> PathBuf {
     dirs::config_dir()
@@ -26,31 +42,139 @@ pub fn config_dir() -> PathBuf {
         .join(APP_NAME)
 }
 
-#[derive(Default, Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Config {
+    /// Layout of the file, older files are migrated when loaded
+    #[serde(default)]
+    pub version: u32,
     pub general: GeneralConfig,
     pub ripping: SampleRippingConfig,
     pub naming: SampleNameConfig,
 }
 
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: VERSION,
+            general: GeneralConfig::default(),
+            ripping: SampleRippingConfig::default(),
+            naming: SampleNameConfig::default(),
+        }
+    }
+}
+
 impl Config {
     pub fn load() -> Self {
+        Self::load_recovered().0
+    }
+
+    /// Load the configuration, keeping every setting that can still be read.
+    ///
+    /// If anything had to be dropped or migrated, the original file is backed up
+    /// so that it won't be lost when the configuration is saved.
+    pub fn load_recovered() -> (Self, Recovery) {
         let Ok(toml) = fs::read_to_string(Self::path()) else {
             info!("Generating Default config file. Note that this won't be saved.");
-            return Self::default();
+            return (Self::default(), Recovery::default());
         };
 
-        Self::load_str(&toml)
+        let (config, mut recovery) = Self::recover_str(&toml);
+
+        if recovery.is_lossy() {
+            recovery.backup = Self::backup(&toml);
+        }
+
+        (config, recovery)
     }
 
     pub fn load_str(input: &str) -> Self {
-        toml::from_str(input).unwrap_or_else(|e| {
-            warn!(
-                "Could not parse existing configuration file. Perhaps an older version was loaded?"
-            );
-            error!("{}", e);
-            Self::default()
-        })
+        Self::recover_str(input).0
+    }
+
+    /// Parse the configuration, migrating it and dropping the keys that can't be read.
+    pub fn recover_str(input: &str) -> (Self, Recovery) {
+        match toml::from_str(input) {
+            Ok(document) => Self::recover_table(document),
+            Err(e) => {
+                warn!("Could not parse existing configuration file.");
+                error!("{}", e);
+                (Self::default(), Recovery::unreadable())
+            }
+        }
+    }
+
+    fn recover_table(mut document: toml::Table) -> (Self, Recovery) {
+        let mut recovery = Recovery::default();
+        recovery::migrate(&mut document, &mut recovery);
+        let config = recovery::recover(document, &mut recovery.dropped);
+
+        (config, recovery)
+    }
+
+    /// Read settings shared by someone else, along with the sections the file contains.
+    ///
+    /// Unlike [load](Self::load), this fails if the file isn't a valid configuration file.
+    pub async fn import_file(path: &Path) -> Result<(Self, Sections, Recovery)> {
+        let toml = tokio::fs::read_to_string(path).await?;
+        let document: toml::Table = toml::from_str(&toml)?;
+        let sections = Sections::present(&document);
+        let (config, recovery) = Self::recover_table(document);
+
+        Ok((config, sections, recovery))
+    }
+
+    pub async fn export_file(&self, path: &Path) -> Result<()> {
+        tokio::fs::write(path, self.save_str()?).await?;
+        info!("Exported configuration to: {}", path.display());
+        Ok(())
+    }
+
+    /// Replace the selected sections with the ones from another configuration
+    ///
+    /// Paths only make sense on the machine they were set on,
+    /// so the destination and logging path are kept.
+    pub fn import(&mut self, other: Config, sections: Sections) {
+        if sections.general {
+            let logging_path = self.general.logging_path.take();
+            self.general = other.general;
+            self.general.logging_path = logging_path;
+        }
+
+        if sections.ripping {
+            let destination = std::mem::take(&mut self.ripping.destination);
+            self.ripping = other.ripping;
+            self.ripping.destination = destination;
+        }
+
+        if sections.naming {
+            self.naming = other.naming;
+        }
+    }
+
+    fn backup(contents: &str) -> Option<PathBuf> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or_default();
+
+        let path = (0..)
+            .map(|n| match n {
+                0 => format!("{BACKUP_PREFIX}.{timestamp}.toml"),
+                n => format!("{BACKUP_PREFIX}.{timestamp}-{n}.toml"),
+            })
+            .map(|name| config_dir().join(name))
+            .find(|path| !path.exists())?;
+
+        match fs::write(&path, contents) {
+            Ok(()) => {
+                info!("Backed up configuration file to: {}", path.display());
+                Some(path)
+            }
+            Err(e) => {
+                error!("Could not back up configuration file: {}", e);
+                None
+            }
+        }
     }
 
     pub fn save_str(&self) -> anyhow::Result<String> {
@@ -95,3 +219,45 @@ impl Config {
         config_dir().join(Self::filename())
     }
 }
+
+/// Parts of the configuration that can be imported on their own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sections {
+    pub general: bool,
+    pub ripping: bool,
+    pub naming: bool,
+}
+
+impl Default for Sections {
+    fn default() -> Self {
+        Self {
+            general: true,
+            ripping: true,
+            naming: true,
+        }
+    }
+}
+
+impl Sections {
+    pub fn any(&self) -> bool {
+        self.general || self.ripping || self.naming
+    }
+
+    /// The sections written in a configuration file
+    pub fn present(document: &toml::Table) -> Self {
+        Self {
+            general: document.contains_key("general"),
+            ripping: document.contains_key("ripping"),
+            naming: document.contains_key("naming"),
+        }
+    }
+
+    /// Sections selected in both
+    pub fn and(self, other: Self) -> Self {
+        Self {
+            general: self.general && other.general,
+            ripping: self.ripping && other.ripping,
+            naming: self.naming && other.naming,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Keep the command line overrides out of the configuration file
+
+use super::{Config, VERSION};
+
+/// What the configuration file was last loaded or saved with.
+///
+/// The command line overrides only last for the session,
+/// so sections that haven't changed since then are saved as they were on disk.
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    /// The configuration on disk
+    saved: Config,
+    /// The settings in use when it was loaded or saved, including the command line overrides
+    applied: Config,
+}
+
+impl Baseline {
+    /// ``applied`` is ``saved`` with the overrides, or the settings that were saved as ``saved``.
+    pub fn new(saved: Config, applied: Config) -> Self {
+        Self { saved, applied }
+    }
+
+    /// What the configuration file should contain with the current settings.
+    pub fn persisted(&self, current: &Config) -> Config {
+        Config {
+            version: VERSION,
+            general: pick(&current.general, &self.applied.general, &self.saved.general),
+            ripping: pick(&current.ripping, &self.applied.ripping, &self.saved.ripping),
+            naming: pick(&current.naming, &self.applied.naming, &self.saved.naming),
+        }
+    }
+
+    /// Have the settings changed since they were last saved?
+    pub fn is_dirty(&self, current: &Config) -> bool {
+        let persisted = self.persisted(current);
+
+        persisted.general != self.saved.general
+            || persisted.ripping != self.saved.ripping
+            || persisted.naming != self.saved.naming
+    }
+}
+
+fn pick<T: Clone + PartialEq>(current: &T, applied: &T, saved: &T) -> T {
+    match current == applied {
+        true => saved.clone(),
+        false => current.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// Launched with the destination overridden from the command line
+    fn launch() -> (Baseline, Config) {
+        let saved = Config::default();
+        let mut applied = saved.clone();
+        applied.ripping.destination = PathBuf::from("override");
+
+        (Baseline::new(saved, applied.clone()), applied)
+    }
+
+    /// Save the settings like the application does
+    fn save(baseline: &Baseline, current: &Config) -> Baseline {
+        Baseline::new(baseline.persisted(current), current.clone())
+    }
+
+    #[test]
+    fn overrides_arent_saved() {
+        let (baseline, mut current) = launch();
+        assert!(!baseline.is_dirty(&current));
+
+        current.naming.upper = !current.naming.upper;
+        assert!(baseline.is_dirty(&current));
+
+        let persisted = baseline.persisted(&current);
+        assert_eq!(persisted.ripping, Config::default().ripping);
+        assert_eq!(persisted.naming, current.naming);
+    }
+
+    #[test]
+    fn section_can_be_reverted_after_saving() {
+        let (launched, mut current) = launch();
+        let original = current.naming;
+
+        current.naming.upper = !current.naming.upper;
+        let baseline = save(&launched, &current);
+        assert!(!baseline.is_dirty(&current));
+
+        // Back to the settings the application was launched with
+        current.naming = original;
+        assert!(baseline.is_dirty(&current));
+
+        let baseline = save(&baseline, &current);
+        assert!(!baseline.is_dirty(&current));
+        assert_eq!(baseline.persisted(&current).naming, original);
+        assert_eq!(
+            baseline.persisted(&current).ripping,
+            Config::default().ripping
+        );
+    }
+
+    #[test]
+    fn changed_override_is_saved() {
+        let (baseline, mut current) = launch();
+
+        current.ripping.destination = PathBuf::from("elsewhere");
+        let baseline = save(&baseline, &current);
+        assert_eq!(
+            baseline.persisted(&current).ripping.destination,
+            PathBuf::from("elsewhere")
+        );
+
+        // Reverting to the override saves it too, it's no longer the launch value
+        current.ripping.destination = PathBuf::from("override");
+        assert!(baseline.is_dirty(&current));
+        assert_eq!(
+            baseline.persisted(&current).ripping.destination,
+            PathBuf::from("override")
+        );
+    }
+}
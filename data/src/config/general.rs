@@ -6,16 +6,19 @@ use crate::theme::Themes;
 
 use super::name_params::SampleNameParams;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(default)]
 pub struct GeneralConfig {
     pub theme: Themes,
     pub logging_path: Option<PathBuf>,
+    pub error_log_format: ErrorLogFormat,
     pub non_gui_quiet_output: bool,
     pub non_gui_use_cwd: bool,
     pub hide_gif: bool,
     pub suppress_warnings: bool,
     pub show_errors_in_text_editor: bool,
+    /// Save the configuration as soon as it's changed
+    pub auto_save: bool,
     pub sample_name_params: SampleNameParams,
 }
 
@@ -23,6 +26,7 @@ impl Default for GeneralConfig {
     fn default() -> Self {
         Self {
             logging_path: None,
+            error_log_format: ErrorLogFormat::default(),
             non_gui_quiet_output: false,
             non_gui_use_cwd: false,
             hide_gif: false,
@@ -30,6 +34,40 @@ impl Default for GeneralConfig {
             theme: Themes::default(),
             sample_name_params: SampleNameParams::default(),
             show_errors_in_text_editor: true,
+            auto_save: false,
         }
     }
 }
+
+/// How errors are written to a log file
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorLogFormat {
+    #[default]
+    Text,
+    /// One JSON object per line
+    JsonLines,
+}
+
+impl ErrorLogFormat {
+    pub const ALL: &'static [Self] = &[Self::Text, Self::JsonLines];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::JsonLines => "jsonl",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorLogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ErrorLogFormat::Text => "Text",
+                ErrorLogFormat::JsonLines => "JSON Lines",
+            }
+        )
+    }
+}
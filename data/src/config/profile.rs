@@ -0,0 +1,130 @@
+//! Named sets of ripping and naming settings.
+//!
+//! Each profile is stored as its own file in the "profiles" folder of the config directory.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use super::{config_dir, Config, SampleNameConfig, SampleRippingConfig};
+
+const PROFILE_DIR: &str = "profiles";
+const EXTENSION: &str = "toml";
+
+#[derive(Default, Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Profile {
+    pub ripping: SampleRippingConfig,
+    pub naming: SampleNameConfig,
+}
+
+impl Profile {
+    /// Take the ripping and naming settings from the configuration
+    pub fn new(ripping: &SampleRippingConfig, naming: &SampleNameConfig) -> Self {
+        Self {
+            ripping: ripping.clone(),
+            naming: *naming,
+        }
+    }
+
+    /// Replace the ripping and naming settings of the configuration
+    pub fn apply(self, config: &mut Config) {
+        config.ripping = self.ripping;
+        config.naming = self.naming;
+    }
+
+    pub fn dir() -> PathBuf {
+        config_dir().join(PROFILE_DIR)
+    }
+
+    pub fn path(name: &str) -> PathBuf {
+        Self::dir().join(format!("{name}.{EXTENSION}"))
+    }
+
+    pub fn exists(name: &str) -> bool {
+        Self::is_valid_name(name) && Self::path(name).is_file()
+    }
+
+    /// Profile names are used as file names, so they can't contain path separators
+    /// or characters that some platforms reject.
+    pub fn is_valid_name(name: &str) -> bool {
+        const RESERVED: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+        !name.trim().is_empty()
+            && name.trim() == name
+            && !name.starts_with('.')
+            && !name.contains(RESERVED)
+    }
+
+    /// Names of the saved profiles, sorted alphabetically
+    pub fn list() -> Vec<String> {
+        let Ok(entries) = fs::read_dir(Self::dir()) else {
+            return Vec::new();
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == EXTENSION))
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_owned()))
+            .collect();
+
+        names.sort_by_key(|name| name.to_lowercase());
+        names
+    }
+
+    pub fn load(name: &str) -> Result<Self> {
+        let toml = fs::read_to_string(Self::path(name))?;
+        Ok(toml::from_str(&toml)?)
+    }
+
+    pub async fn save(&self, name: &str) -> Result<()> {
+        if !Self::is_valid_name(name) {
+            bail!("\"{}\" is not a valid profile name", name);
+        }
+
+        tokio::fs::create_dir_all(Self::dir()).await?;
+        tokio::fs::write(Self::path(name), toml::to_string_pretty(&self)?).await?;
+
+        info!("Saved profile: {}", name);
+        Ok(())
+    }
+
+    pub async fn duplicate(name: &str, new_name: &str) -> Result<()> {
+        Self::check_available(new_name)?;
+        tokio::fs::copy(Self::path(name), Self::path(new_name)).await?;
+
+        info!("Duplicated profile: {} -> {}", name, new_name);
+        Ok(())
+    }
+
+    pub async fn rename(name: &str, new_name: &str) -> Result<()> {
+        Self::check_available(new_name)?;
+        tokio::fs::rename(Self::path(name), Self::path(new_name)).await?;
+
+        info!("Renamed profile: {} -> {}", name, new_name);
+        Ok(())
+    }
+
+    pub async fn delete(name: &str) -> Result<()> {
+        tokio::fs::remove_file(Self::path(name)).await?;
+
+        info!("Deleted profile: {}", name);
+        Ok(())
+    }
+
+    fn check_available(name: &str) -> Result<()> {
+        if !Self::is_valid_name(name) {
+            bail!("\"{}\" is not a valid profile name", name);
+        }
+
+        if Self::path(name).exists() {
+            bail!("A profile named \"{}\" already exists", name);
+        }
+
+        Ok(())
+    }
+}
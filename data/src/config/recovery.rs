@@ -0,0 +1,267 @@
+//! Loading configuration files written by other versions of XMODITS, or edited by hand.
+//!
+//! The file is migrated to the current [VERSION] first.
+//! Then, instead of discarding everything when a single value can't be read,
+//! the file is merged on top of the defaults one key at a time, keeping every key that is still valid.
+
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use toml::{Table, Value};
+use tracing::{info, warn};
+
+/// Current version of the configuration file
+pub const VERSION: u32 = 1;
+
+/// Upgrades a file from one version to the next, the first entry upgrades version 0.
+const MIGRATIONS: &[fn(&mut Table)] = &[v0_to_v1];
+
+/// What happened while loading the configuration
+#[derive(Debug, Default, Clone)]
+pub struct Recovery {
+    /// Keys that couldn't be read and were reset to their defaults, e.g. "ripping.exported_format"
+    pub dropped: Vec<String>,
+    /// Version of the file if it had to be migrated
+    pub migrated_from: Option<u32>,
+    /// Copy of the file as it was before anything was dropped or migrated
+    pub backup: Option<PathBuf>,
+}
+
+impl Recovery {
+    /// The whole file couldn't be read
+    pub fn unreadable() -> Self {
+        Self {
+            dropped: vec!["(entire file)".to_owned()],
+            ..Default::default()
+        }
+    }
+
+    /// The file should be backed up before it is overwritten
+    pub fn is_lossy(&self) -> bool {
+        !self.dropped.is_empty() || self.migrated_from.is_some()
+    }
+}
+
+impl std::fmt::Display for Recovery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(version) = self.migrated_from {
+            write!(
+                f,
+                "The configuration was upgraded from version {}. ",
+                version
+            )?;
+        }
+
+        if !self.dropped.is_empty() {
+            write!(
+                f,
+                "Some settings couldn't be read and were reset: {}. ",
+                self.dropped.join(", ")
+            )?;
+        }
+
+        if let Some(backup) = &self.backup {
+            write!(f, "A backup was saved to: {}", backup.display())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Upgrade the document to the current [VERSION]
+pub fn migrate(document: &mut Table, recovery: &mut Recovery) {
+    let version = match document.get("version") {
+        Some(Value::Integer(version)) => u32::try_from(*version).unwrap_or_default(),
+        _ => 0,
+    };
+
+    if version > VERSION {
+        warn!(
+            "The configuration was written by a newer version of XMODITS (version {})",
+            version
+        );
+    }
+
+    if version < VERSION {
+        info!(
+            "Migrating configuration from version {} to {}",
+            version, VERSION
+        );
+        recovery.migrated_from = Some(version);
+
+        MIGRATIONS[version as usize..]
+            .iter()
+            .for_each(|migration| migration(document));
+    }
+
+    document.insert("version".into(), Value::Integer(VERSION.into()));
+}
+
+/// Files written before versioning have the same layout as version 1.
+fn v0_to_v1(_: &mut Table) {}
+
+/// Merge the document on top of the defaults, one key at a time.
+///
+/// Keys that make the document invalid are left out and recorded in `dropped`.
+pub fn recover<T>(document: Table, dropped: &mut Vec<String>) -> T
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    if let Ok(value) = Value::Table(document.clone()).try_into() {
+        return value;
+    }
+
+    let Ok(Value::Table(mut merged)) = Value::try_from(T::default()) else {
+        return T::default();
+    };
+
+    let is_valid = |merged: &Table| Value::Table(merged.clone()).try_into::<T>().is_ok();
+    merge(&mut merged, &mut Vec::new(), document, &is_valid, dropped);
+
+    Value::Table(merged).try_into().unwrap_or_default()
+}
+
+fn merge(
+    merged: &mut Table,
+    parent: &mut Vec<String>,
+    document: Table,
+    is_valid: &impl Fn(&Table) -> bool,
+    dropped: &mut Vec<String>,
+) {
+    for (key, value) in document {
+        let previous = table_at(merged, parent).insert(key.clone(), value.clone());
+
+        if is_valid(merged) {
+            continue;
+        }
+
+        let nested = matches!(previous, Some(Value::Table(_)));
+        let table = table_at(merged, parent);
+
+        match previous {
+            Some(previous) => table.insert(key.clone(), previous),
+            None => table.remove(&key),
+        };
+
+        parent.push(key);
+
+        match value {
+            // Keep what can be kept from nested sections
+            Value::Table(value) if nested => merge(merged, parent, value, is_valid, dropped),
+            _ => {
+                warn!("Dropped invalid configuration key: {}", parent.join("."));
+                dropped.push(parent.join("."));
+            }
+        }
+
+        parent.pop();
+    }
+}
+
+/// The nested table at the given path, the path must lead to a table.
+fn table_at<'a>(table: &'a mut Table, path: &[String]) -> &'a mut Table {
+    path.iter()
+        .fold(table, |table, key| match table.get_mut(key) {
+            Some(Value::Table(nested)) => nested,
+            _ => unreachable!("path should lead to a table"),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use toml::Table;
+
+    use super::{migrate, recover, Recovery, VERSION};
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    struct Config {
+        name: String,
+        count: u32,
+        nested: Nested,
+    }
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(default)]
+    struct Nested {
+        enabled: bool,
+        level: u8,
+    }
+
+    fn load(document: &str) -> (Config, Vec<String>) {
+        let document: Table = toml::from_str(document).unwrap();
+        let mut dropped = Vec::new();
+        let config = recover(document, &mut dropped);
+
+        (config, dropped)
+    }
+
+    #[test]
+    fn valid_document() {
+        let (config, dropped) = load("name = \"a\"\ncount = 2\n[nested]\nlevel = 3");
+
+        assert!(dropped.is_empty());
+        assert_eq!(config.name, "a");
+        assert_eq!(config.count, 2);
+        assert_eq!(config.nested.level, 3);
+    }
+
+    #[test]
+    fn bad_value_is_dropped() {
+        let (config, dropped) = load("name = \"a\"\ncount = \"lots\"");
+
+        assert_eq!(dropped, ["count"]);
+        assert_eq!(config.name, "a");
+        assert_eq!(config.count, 0);
+    }
+
+    #[test]
+    fn nested_tables_keep_valid_keys() {
+        let (config, dropped) = load("count = 2\n[nested]\nenabled = true\nlevel = 999");
+
+        assert_eq!(dropped, ["nested.level"]);
+        assert_eq!(config.count, 2);
+        assert_eq!(
+            config.nested,
+            Nested {
+                enabled: true,
+                level: 0
+            }
+        );
+    }
+
+    #[test]
+    fn nested_table_replaced_by_value() {
+        let (config, dropped) = load("count = 2\nnested = 5");
+
+        assert_eq!(dropped, ["nested"]);
+        assert_eq!(config.count, 2);
+        assert_eq!(config.nested, Nested::default());
+    }
+
+    #[test]
+    fn unknown_keys_are_ignored() {
+        let (config, dropped) =
+            load("unknown = 1\ncount = \"lots\"\n[nested]\nlevel = 3\nextra = 2");
+
+        assert_eq!(dropped, ["count"]);
+        assert_eq!(config.nested.level, 3);
+    }
+
+    #[test]
+    fn unversioned_document_is_migrated() {
+        let mut document: Table = toml::from_str("count = 2").unwrap();
+        let mut recovery = Recovery::default();
+        migrate(&mut document, &mut recovery);
+
+        assert_eq!(recovery.migrated_from, Some(0));
+        assert_eq!(
+            document
+                .get("version")
+                .and_then(|version| version.as_integer()),
+            Some(VERSION.into())
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+/// Convert the sample rate and bit depth of exported samples.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default)]
+pub struct SampleConversionConfig {
+    /// Target sample rate. 0 keeps the original rate.
+    pub rate: u32,
+    pub bit_depth: BitDepth,
+    pub resampler: Resampler,
+    /// Apply dither when the bit depth is reduced.
+    pub dither: bool,
+}
+
+impl Default for SampleConversionConfig {
+    fn default() -> Self {
+        Self {
+            rate: 0,
+            bit_depth: BitDepth::default(),
+            resampler: Resampler::default(),
+            dither: true,
+        }
+    }
+}
+
+impl SampleConversionConfig {
+    pub fn is_active(&self) -> bool {
+        self.rate != 0 || self.bit_depth != BitDepth::Original
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BitDepth {
+    #[default]
+    Original,
+    Bits8,
+    Bits16,
+    Bits24,
+    Float32,
+}
+
+impl BitDepth {
+    pub const ALL: &'static [Self] = &[
+        Self::Original,
+        Self::Bits8,
+        Self::Bits16,
+        Self::Bits24,
+        Self::Float32,
+    ];
+}
+
+impl std::fmt::Display for BitDepth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                BitDepth::Original => "Original",
+                BitDepth::Bits8 => "8-bit",
+                BitDepth::Bits16 => "16-bit",
+                BitDepth::Bits24 => "24-bit",
+                BitDepth::Float32 => "32-bit Float",
+            }
+        )
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Resampler {
+    Linear,
+    #[default]
+    Cubic,
+    Sinc,
+}
+
+impl Resampler {
+    pub const ALL: &'static [Self] = &[Self::Linear, Self::Cubic, Self::Sinc];
+}
+
+impl std::fmt::Display for Resampler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Resampler::Linear => "Linear",
+                Resampler::Cubic => "Cubic",
+                Resampler::Sinc => "Windowed Sinc",
+            }
+        )
+    }
+}
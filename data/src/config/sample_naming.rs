@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use xmodits_lib::{SampleNamer, SampleNamerTrait};
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 #[serde(default)]
 pub struct SampleNameConfig {
     pub index_raw: bool,
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// Optional processing applied to decoded samples before they're exported.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct SampleProcessingConfig {
+    pub remove_dc_offset: bool,
+    pub trim_silence: bool,
+    /// Samples quieter than this (in dBFS) are considered silent.
+    pub silence_threshold: i8,
+    pub normalize: Normalize,
+    /// Target level of the normalized sample (in dBFS).
+    pub normalize_target: i8,
+    /// Length of the de-click fade in (in milliseconds).
+    pub fade_in: u16,
+    /// Length of the de-click fade out (in milliseconds).
+    pub fade_out: u16,
+}
+
+impl Default for SampleProcessingConfig {
+    fn default() -> Self {
+        Self {
+            remove_dc_offset: false,
+            trim_silence: false,
+            silence_threshold: -60,
+            normalize: Normalize::default(),
+            normalize_target: -1,
+            fade_in: 0,
+            fade_out: 0,
+        }
+    }
+}
+
+impl SampleProcessingConfig {
+    /// Returns true if any stage of the processing chain is enabled.
+    pub fn is_active(&self) -> bool {
+        self.remove_dc_offset
+            || self.trim_silence
+            || self.normalize != Normalize::Off
+            || self.fade_in > 0
+            || self.fade_out > 0
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Normalize {
+    #[default]
+    Off,
+    Peak,
+    Rms,
+}
+
+impl Normalize {
+    pub const ALL: &'static [Self] = &[Self::Off, Self::Peak, Self::Rms];
+}
+
+impl std::fmt::Display for Normalize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Normalize::Off => "Off",
+                Normalize::Peak => "Peak",
+                Normalize::Rms => "RMS",
+            }
+        )
+    }
+}
@@ -1,10 +1,11 @@
 pub use super::SampleNameConfig;
+use super::{SampleConversionConfig, SampleProcessingConfig};
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use xmodits_lib::exporter::AudioFormat;
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(default)]
 pub struct SampleRippingConfig {
     pub destination: PathBuf,
@@ -12,7 +13,25 @@ pub struct SampleRippingConfig {
     pub folder_max_depth: u8,
     pub strict: bool,
     pub worker_threads: usize,
-    pub exported_format: AudioFormat,
+    pub exported_format: ExportFormat,
+    pub channels: Channels,
+    pub embed_loop_points: bool,
+    pub embed_tags: bool,
+    pub generate_sfz: bool,
+    pub generate_sf2: bool,
+    /// Give every sample its own preset in the SoundFont, besides the drum kit preset.
+    pub sf2_sample_presets: bool,
+    pub sidecar: Sidecar,
+    pub static_noise: StaticNoise,
+    /// Skip samples shorter than this many frames, 0 disables it.
+    pub min_frames: u32,
+    pub skip_silent: bool,
+    /// Samples whose peak stays below this (in dBFS) are considered silent.
+    pub silence_threshold: i8,
+    /// Skip samples that have the exact same PCM data as an earlier sample in the module.
+    pub skip_duplicates: bool,
+    pub processing: SampleProcessingConfig,
+    pub conversion: SampleConversionConfig,
 }
 
 impl Default for SampleRippingConfig {
@@ -23,7 +42,21 @@ impl Default for SampleRippingConfig {
             folder_max_depth: 4,
             strict: true,
             exported_format: Default::default(),
+            channels: Channels::default(),
+            embed_loop_points: false,
+            embed_tags: false,
+            generate_sfz: false,
+            generate_sf2: false,
+            sf2_sample_presets: true,
+            sidecar: Sidecar::default(),
+            static_noise: StaticNoise::default(),
+            min_frames: 0,
+            skip_silent: false,
+            silence_threshold: -60,
+            skip_duplicates: false,
             worker_threads: 0,
+            processing: SampleProcessingConfig::default(),
+            conversion: SampleConversionConfig::default(),
         }
     }
 }
@@ -32,3 +65,166 @@ fn default_dir() -> PathBuf {
     let fallback = || std::env::current_dir().unwrap_or_default();
     dirs::download_dir().unwrap_or_else(fallback)
 }
+
+/// How the channels of exported samples are laid out
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Channels {
+    /// Mono samples stay mono, stereo samples stay stereo
+    #[default]
+    Keep,
+    /// Mix stereo samples down to mono
+    Mono,
+    /// Duplicate mono samples to both channels
+    Stereo,
+    /// Write each channel of a stereo sample to its own file, suffixed with ``_L`` and ``_R``
+    Split,
+}
+
+impl Channels {
+    pub const ALL: &'static [Self] = &[Self::Keep, Self::Mono, Self::Stereo, Self::Split];
+}
+
+impl std::fmt::Display for Channels {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Channels::Keep => "Keep",
+                Channels::Mono => "Downmix to Mono",
+                Channels::Stereo => "Force Stereo",
+                Channels::Split => "Split Stereo",
+            }
+        )
+    }
+}
+
+/// What to do with samples that are most likely static noise
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StaticNoise {
+    #[default]
+    Keep,
+    SkipSamples,
+    /// Skip modules where most of the samples are noise, as well as any other noisy samples
+    SkipModules,
+}
+
+impl StaticNoise {
+    pub const ALL: &'static [Self] = &[Self::Keep, Self::SkipSamples, Self::SkipModules];
+}
+
+impl std::fmt::Display for StaticNoise {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                StaticNoise::Keep => "Keep",
+                StaticNoise::SkipSamples => "Skip Samples",
+                StaticNoise::SkipModules => "Skip Samples & Modules",
+            }
+        )
+    }
+}
+
+/// Write JSON files describing the exported samples
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Sidecar {
+    #[default]
+    Off,
+    /// ``<sample>.json`` next to each exported sample
+    PerSample,
+    /// A single ``samples.json`` for each module
+    PerModule,
+}
+
+impl Sidecar {
+    pub const ALL: &'static [Self] = &[Self::Off, Self::PerSample, Self::PerModule];
+}
+
+impl std::fmt::Display for Sidecar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Sidecar::Off => "Off",
+                Sidecar::PerSample => "Per Sample",
+                Sidecar::PerModule => "Per Module",
+            }
+        )
+    }
+}
+
+/// Audio formats that samples can be exported to.
+///
+/// Variants share their names with xmodits' [AudioFormat] so existing configs still load.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    #[default]
+    WAV,
+    AIFF,
+    FLAC,
+    ITS,
+    S3I,
+    IFF,
+    RAW,
+}
+
+impl ExportFormat {
+    pub const ALL: &'static [Self] = &[
+        Self::WAV,
+        Self::AIFF,
+        Self::FLAC,
+        Self::ITS,
+        Self::S3I,
+        Self::IFF,
+        Self::RAW,
+    ];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::WAV => "wav",
+            Self::AIFF => "aiff",
+            Self::FLAC => "flac",
+            Self::ITS => "its",
+            Self::S3I => "s3i",
+            Self::IFF => "8svx",
+            Self::RAW => "raw",
+        }
+    }
+
+    /// The equivalent xmodits format, FLAC isn't provided by xmodits.
+    pub fn audio_format(&self) -> Option<AudioFormat> {
+        match self {
+            Self::WAV => Some(AudioFormat::WAV),
+            Self::AIFF => Some(AudioFormat::AIFF),
+            Self::FLAC => None,
+            Self::ITS => Some(AudioFormat::ITS),
+            Self::S3I => Some(AudioFormat::S3I),
+            Self::IFF => Some(AudioFormat::IFF),
+            Self::RAW => Some(AudioFormat::RAW),
+        }
+    }
+}
+
+impl std::fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim_start_matches('.');
+
+        Self::ALL
+            .iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or(())
+    }
+}
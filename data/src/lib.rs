@@ -8,16 +8,9 @@ pub use config::Config;
 pub use theme::Theme;
 pub use time::Time;
 
-use xmodits_lib::exporter::AudioFormat;
+use config::sample_ripping::ExportFormat;
 
-pub const SUPPORTED_FORMATS: &[AudioFormat] = &[
-    AudioFormat::WAV,
-    AudioFormat::AIFF,
-    AudioFormat::ITS,
-    AudioFormat::S3I,
-    AudioFormat::IFF,
-    AudioFormat::RAW,
-];
+pub const SUPPORTED_FORMATS: &[ExportFormat] = ExportFormat::ALL;
 
 #[cfg(feature = "manual")]
 pub static MANUAL: &str = include_str!("../../assets/manual.txt");
\ No newline at end of file
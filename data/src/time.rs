@@ -4,6 +4,10 @@ use std::time::{Duration, Instant};
 pub struct Time {
     start: Instant,
     duration: Duration,
+    /// When the timer was paused, if it's paused
+    paused_since: Option<Instant>,
+    /// Time spent paused, not counting the current pause
+    paused: Duration,
 }
 
 impl Default for Time {
@@ -11,6 +15,8 @@ impl Default for Time {
         Self {
             start: Instant::now(),
             duration: Default::default(),
+            paused_since: None,
+            paused: Duration::ZERO,
         }
     }
 }
@@ -18,10 +24,25 @@ impl Default for Time {
 impl Time {
     pub fn start(&mut self) {
         self.start = Instant::now();
+        self.paused_since = None;
+        self.paused = Duration::ZERO;
+    }
+
+    /// Time spent paused isn't counted.
+    pub fn set_paused(&mut self, paused: bool) {
+        match (paused, self.paused_since) {
+            (true, None) => self.paused_since = Some(Instant::now()),
+            (false, Some(since)) => {
+                self.paused += since.elapsed();
+                self.paused_since = None;
+            }
+            _ => (),
+        }
     }
 
     pub fn stop(&mut self) {
-        self.duration = self.start.elapsed();
+        self.set_paused(false);
+        self.duration = self.start.elapsed().saturating_sub(self.paused);
     }
 
     pub fn elapsed(&self) -> f32 {
@@ -50,3 +71,24 @@ impl std::fmt::Display for Time {
         write!(f, "Took {}", s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paused_time_isnt_counted() {
+        let mut time = Time::init();
+
+        time.set_paused(true);
+        std::thread::sleep(Duration::from_millis(100));
+        time.set_paused(false);
+
+        // Stopping while paused ends the pause
+        time.set_paused(true);
+        std::thread::sleep(Duration::from_millis(100));
+        time.stop();
+
+        assert!(time.elapsed() < 0.05, "{}", time.elapsed());
+    }
+}
@@ -1,34 +1,52 @@
 #[cfg(windows)]
 mod simple;
 
+use crate::cli::Overrides;
+use crate::dialog;
 use crate::event;
 use crate::font;
 use crate::icon;
 use crate::ripper;
+use crate::ripper::activity::Feed;
+use crate::ripper::extraction::error::Failed;
+use crate::ripper::extraction::ErrorHandler;
+use crate::ripper::progress::Stats;
+use crate::ripper::stop_flag::StopToken;
+use crate::ripper::subscription::CompleteState;
 use crate::screen::about;
 use crate::screen::config::name_preview;
+use crate::screen::config::sample_conversion;
 use crate::screen::config::sample_naming;
+use crate::screen::config::sample_processing;
 use crate::screen::config::sample_ripping::{self, DESTINATION_BAR_ID};
 use crate::screen::crash::{self, Crashes};
 use crate::screen::entry::Entries;
+use crate::screen::error_browser::{self, ErrorBrowser};
+use crate::screen::profiles::{self, Profiles};
+use crate::screen::queue::{self, QueuePanel};
 use crate::screen::ripping;
 use crate::screen::sample_player;
 use crate::screen::settings;
+use crate::screen::settings::transfer::{self, Transfer};
 use crate::screen::tracker_info::{self, TrackerInfo};
 use crate::theme;
 use crate::utils::{files_dialog, folders_dialog};
 use crate::widget::helpers::{action, text_icon, warning};
 use crate::widget::{Container, Element};
 
+use data::config::{Baseline, Profile, Recovery, Sections};
 use data::Config;
 pub use ripping::RippingState;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use iced::multi_window::{self, Application};
-use iced::widget::{button, checkbox, column, row, text, text_input, Space};
+use iced::widget::{button, checkbox, column, row, scrollable, text, text_input, Space};
 use iced::{window, Alignment, Command, Length, Size, Subscription};
 
 const TITLE: &str = "XMODITS";
+/// How often the settings are checked for changes when auto-save is enabled
+const AUTO_SAVE_INTERVAL: Duration = Duration::from_secs(2);
 const WINDOW_SIZE: Size = Size::new(780.0, 720.0);
 
 #[derive(Debug, Clone)]
@@ -36,11 +54,21 @@ pub enum Message {
     AboutPressed,
     About(about::Message),
     Add(Option<Vec<PathBuf>>),
+    AddToQueue,
+    AutoSave,
+    BrowseErrors,
     Cancel,
     Clear,
+    ClosePrompt(Option<bool>),
+    CloseSaveFailed(String),
     ConfigPressed,
+    ConversionCfg(sample_conversion::Message),
     DeleteSelected,
+    DismissRecovery,
+    ErrorBrowser(error_browser::Message),
     Event(event::Event),
+    Exit,
+    ExportPressed,
     FileDialog,
     FolderDialog,
     FontLoaded(Result<(), iced::font::Error>),
@@ -49,13 +77,21 @@ pub enum Message {
     InvertSelection,
     NamingCfg(sample_naming::Message),
     Open(String),
+    Pause,
     PreviewSamples(PathBuf),
     Probe(usize),
     ProbeResult(TrackerInfo),
+    ProcessingCfg(sample_processing::Message),
+    Profiles(profiles::Message),
+    Queue(queue::Message),
+    QueuePressed,
+    Resume,
+    RetryFailed,
+    RetryFailedResult(Result<Vec<Failed>, String>),
     RippingCfg(sample_ripping::Message),
     SamplePlayer(sample_player::Message),
     SaveConfig,
-    SaveConfigResult(),
+    SaveConfigResult(Result<Baseline, String>),
     SaveErrors,
     SaveErrorsResult(Result<PathBuf, String>),
     Select { index: usize, selected: bool },
@@ -64,6 +100,7 @@ pub enum Message {
     SettingsPressed,
     StartRipping,
     Subscription(ripper::Message),
+    Transfer(transfer::Message),
     Crashes(crash::Message),
 }
 
@@ -72,10 +109,25 @@ pub enum Message {
 pub enum View {
     #[default]
     Configure,
+    Export,
+    Queue,
     Settings,
     About,
 }
 
+/// What the application starts with
+pub struct Flags {
+    config: Config,
+    /// The configuration as it is on disk, without the command line overrides
+    saved: Config,
+    /// Profile picked from the command line
+    profile: Option<String>,
+    /// Settings that couldn't be loaded
+    recovery: Recovery,
+    /// Error log of the modules to rip again
+    retry_from: Option<PathBuf>,
+}
+
 /// XMODITS graphical application
 #[derive(Default)]
 pub struct XMODITS {
@@ -86,6 +138,15 @@ pub struct XMODITS {
     ripper: ripper::Handle,
     tracker_info: TrackerInfo,
     crashes: Crashes,
+    error_browser: ErrorBrowser,
+    queue: ripper::Queue,
+    queue_panel: QueuePanel,
+    profiles: Profiles,
+    transfer: Transfer,
+    /// Shown if some settings couldn't be loaded
+    recovery: Option<Recovery>,
+    /// Keeps the command line overrides out of the saved configuration
+    baseline: Baseline,
     sample_player: sample_player::SamplePreview,
     naming_cfg: data::config::SampleNameConfig,
     ripping_cfg: data::config::SampleRippingConfig,
@@ -94,32 +155,47 @@ pub struct XMODITS {
 
 impl XMODITS {
     /// Launch the application
-    pub fn launch() -> iced::Result {
+    pub fn launch(overrides: Overrides) -> iced::Result {
         // load configuration
-        let config = Config::load();
+        let (mut config, recovery) = Config::load_recovered();
+        let saved = config.clone();
+        overrides.apply(&mut config);
+
+        let flags = Flags {
+            config,
+            saved,
+            profile: overrides.profile,
+            recovery,
+            retry_from: overrides.retry_from,
+        };
 
         tracing::info!("Launcing GUI");
-        Self::run(Self::settings(config))
+        Self::run(Self::settings(flags))
     }
 
     /// WINDOWS ONLY
     ///
     /// XMODITS' simple mode to allow dragging and dropping modules onto the binary
     #[cfg(windows)]
-    pub fn launch_simple(paths: impl IntoIterator<Item = String>) -> iced::Result {
-        simple::rip(paths);
+    pub fn launch_simple(
+        paths: impl IntoIterator<Item = String>,
+        overrides: Overrides,
+    ) -> iced::Result {
+        simple::rip(paths, overrides);
         Ok(())
     }
 
-    pub fn settings(config: Config) -> iced::Settings<Config> {
+    pub fn settings(flags: Flags) -> iced::Settings<Flags> {
         iced::Settings {
             default_font: font::JETBRAINS_MONO,
             default_text_size: 13.0.into(),
-            flags: config,
+            flags,
             window: window::Settings {
                 icon: Some(application_icon()),
                 size: WINDOW_SIZE,
                 min_size: Some(WINDOW_SIZE),
+                // Give the user a chance to save their settings
+                exit_on_close_request: false,
                 ..Default::default()
             },
             antialiasing: true,
@@ -127,10 +203,78 @@ impl XMODITS {
         }
     }
 
-    pub fn load_cfg(&mut self, config: Config) {
+    pub fn load_cfg(&mut self, config: Config) -> Command<Message> {
         self.ripping_cfg = config.ripping;
         self.naming_cfg = config.naming;
         self.general_cfg = config.general;
+
+        self.sample_player
+            .set_processing(self.ripping_cfg.processing)
+            .map(Message::SamplePlayer)
+    }
+
+    /// Replace the ripping and naming settings with a saved profile
+    pub fn load_profile(&mut self, name: String) -> Command<Message> {
+        match Profile::load(&name) {
+            Ok(profile) => {
+                self.ripping_cfg = profile.ripping;
+                self.naming_cfg = profile.naming;
+                self.profiles.set_selected(name);
+
+                self.sample_player
+                    .set_processing(self.ripping_cfg.processing)
+                    .map(Message::SamplePlayer)
+            }
+            Err(e) => {
+                tracing::error!("Could not load profile \"{}\": {}", name, e);
+                self.profiles
+                    .set_error(format!("Could not load \"{}\": {}", name, e));
+                Command::none()
+            }
+        }
+    }
+
+    /// Replace the sections picked by the user with imported settings
+    pub fn import_cfg(
+        &mut self,
+        result: Result<(Config, Sections, Recovery), String>,
+    ) -> Command<Message> {
+        let (imported, present, recovery) = match result {
+            Ok(imported) => imported,
+            Err(e) => {
+                tracing::error!("Failed to import settings: {}", e);
+                self.transfer
+                    .set_status(Err(format!("Failed to import settings: {}", e)));
+                return Command::none();
+            }
+        };
+
+        // Sections missing from the file would otherwise be reset to their defaults
+        let sections = self.transfer.sections().and(present);
+
+        if !sections.any() {
+            self.transfer.set_status(Err(
+                "The file doesn't contain any of the selected settings.".to_owned(),
+            ));
+            return Command::none();
+        }
+
+        let mut config = self.current_cfg();
+        config.import(imported, sections);
+
+        self.general_cfg = config.general;
+        self.ripping_cfg = config.ripping;
+        self.naming_cfg = config.naming;
+        self.transfer
+            .set_status(Ok(transfer::imported(sections, &recovery)));
+
+        self.sample_player
+            .set_processing(self.ripping_cfg.processing)
+            .map(Message::SamplePlayer)
+    }
+
+    pub fn current_profile(&self) -> Profile {
+        Profile::new(&self.ripping_cfg, &self.naming_cfg)
     }
 
     pub fn build_start_signal(&mut self) -> ripper::Signal {
@@ -138,57 +282,128 @@ impl XMODITS {
         let entries = self.entries.take();
         let ripping = self.ripping_cfg.to_owned();
         let naming = self.naming_cfg.to_owned();
+        let error_log_format = self.general_cfg.error_log_format;
+        let logging_path = self.general_cfg.logging_path.clone();
 
         ripper::Signal {
             entries,
             ripping,
             naming,
+            error_log_format,
+            logging_path,
+            stop: StopToken::new(),
         }
     }
 
+    /// Entries added while ripping are pending, so they can be cleared at any time.
     pub fn clear_entries(&mut self) {
-        if !self.state.is_ripping() {
-            self.tracker_info.clear();
-            self.entries.clear();
-        } else if self.state.is_finished() {
+        self.tracker_info.clear();
+        self.entries.clear();
+
+        if self.state.is_finished() {
             self.state = RippingState::Idle;
         }
     }
 
     pub fn delete_selected_entries(&mut self) {
-        if !self.state.is_ripping() {
-            self.entries.delete_selected(&mut self.tracker_info);
-        }
+        self.entries.delete_selected(&mut self.tracker_info);
     }
 
     pub fn app_title(&self) -> String {
+        // Mark unsaved settings
+        let title = match self.is_dirty() {
+            true => format!("{TITLE}*"),
+            false => TITLE.to_string(),
+        };
+
         match &self.state {
-            RippingState::Idle | RippingState::Finished { .. } => TITLE.to_string(),
+            RippingState::Idle | RippingState::Finished { .. } => title,
             RippingState::Ripping {
-                message, progress, ..
+                message,
+                stats,
+                paused,
+                ..
             } => {
+                let message = match paused {
+                    true => "Paused",
+                    false => message.as_deref().unwrap_or("Ripping..."),
+                };
                 let info = format!(
-                    "{} - {}%",
-                    message.as_deref().unwrap_or("Ripping..."),
-                    progress.floor()
+                    "{} - {}% - ETA {}",
+                    message,
+                    stats.percentage().floor(),
+                    stats.eta_text()
                 );
-                format!("{TITLE} - {info}")
+                format!("{title} - {info}")
             }
         }
     }
 
-    pub fn save_cfg(&self) -> Command<Message> {
-        let config = data::Config {
+    pub fn current_cfg(&self) -> Config {
+        data::Config {
+            version: data::config::VERSION,
             general: self.general_cfg.clone(),
             ripping: self.ripping_cfg.clone(),
             naming: self.naming_cfg,
-        };
+        }
+    }
+
+    /// The command line overrides only last for this session, see [Baseline].
+    pub fn save_cfg(&self) -> Command<Message> {
+        let current = self.current_cfg();
+        let config = self.baseline.persisted(&current);
+
+        Command::perform(
+            async move {
+                match config.save().await {
+                    Ok(()) => Ok(Baseline::new(config, current)),
+                    Err(e) => Err(e.to_string()),
+                }
+            },
+            Message::SaveConfigResult,
+        )
+    }
 
-        Command::perform(async move { config.save().await }, |_| Message::Ignore)
+    /// Have the settings changed since they were last saved?
+    pub fn is_dirty(&self) -> bool {
+        self.baseline.is_dirty(&self.current_cfg())
     }
 
+    /// Close the main window, asking to save the settings first if they've changed.
+    pub fn close(&self) -> Command<Message> {
+        match self.is_dirty() {
+            false => window::close(window::Id::MAIN),
+            true if self.general_cfg.auto_save => self.save_and_close(),
+            true => Command::perform(dialog::save_before_closing(), Message::ClosePrompt),
+        }
+    }
+
+    pub fn save_and_close(&self) -> Command<Message> {
+        self.save_cfg().map(|result| match result {
+            Message::SaveConfigResult(Ok(_)) => Message::Exit,
+            Message::SaveConfigResult(Err(e)) => Message::CloseSaveFailed(e),
+            result => result,
+        })
+    }
+
+    /// Queue the entries, then start ripping the first job if nothing is being ripped.
     pub fn start_ripping(&mut self) -> Command<Message> {
-        if self.state.is_ripping() | self.entries.is_empty() | !self.ripper.is_active() {
+        if !self.entries.is_empty() {
+            let command = self.enqueue();
+
+            // The destination isn't valid
+            if !self.entries.is_empty() {
+                return command;
+            }
+        }
+
+        self.start_next_job();
+        Command::none()
+    }
+
+    /// Take a snapshot of the entries and configuration as a new job.
+    pub fn enqueue(&mut self) -> Command<Message> {
+        if self.entries.is_empty() {
             return Command::none();
         }
 
@@ -199,29 +414,75 @@ impl XMODITS {
             return text_input::focus(DESTINATION_BAR_ID.clone());
         }
 
-        let start_signal = self.build_start_signal();
+        let signal = self.build_start_signal();
+        self.queue.push(signal);
+
+        Command::none()
+    }
+
+    fn start_next_job(&mut self) {
+        if self.state.is_ripping() | !self.ripper.is_active() {
+            return;
+        }
+
+        let Some(start_signal) = self.queue.next() else {
+            return;
+        };
+
+        self.error_browser = ErrorBrowser::default();
         self.ripper
             .send(start_signal)
             .expect("Sending start signal to Ripper.");
 
         self.state = RippingState::Ripping {
             message: None,
-            progress: 0.0,
-            errors: 0,
+            stats: Stats::default(),
+            feed: Feed::default(),
+            paused: false,
         };
-
-        Command::none()
     }
 
-    fn add_entry(&mut self, path: PathBuf) {
-        self.add_entries(Some(vec![path]))
+    /// Carry on with the queue once the ripper is ready, unless the last job was stopped.
+    fn continue_queue(&mut self) {
+        let RippingState::Finished { state, .. } = &self.state else {
+            return;
+        };
+
+        if !matches!(state, CompleteState::Cancelled | CompleteState::Aborted) {
+            self.start_next_job();
+        }
     }
 
-    fn add_entries(&mut self, paths: Option<Vec<PathBuf>>) {
+    /// Replace the entries with the modules that failed, so they can be ripped again
+    /// once the user has adjusted their configuration.
+    fn retry_failed(&mut self, errors: Vec<Failed>) {
         if self.state.is_ripping() {
             return;
         }
 
+        let paths: Vec<PathBuf> = errors
+            .into_iter()
+            .filter(Failed::can_retry)
+            .map(|error| error.path)
+            .filter(|path| path.exists())
+            .collect();
+
+        tracing::info!("Retrying {} failed module(s)", paths.len());
+
+        self.tracker_info.clear();
+        self.entries.clear();
+        self.entries.add_multiple(paths);
+        self.error_browser = ErrorBrowser::default();
+        self.state = RippingState::Idle;
+        self.view = View::Configure;
+    }
+
+    fn add_entry(&mut self, path: PathBuf) {
+        self.add_entries(Some(vec![path]))
+    }
+
+    /// Entries can be added while ripping, they'll be ripped as the next job.
+    fn add_entries(&mut self, paths: Option<Vec<PathBuf>>) {
         let Some(paths) = paths else { return };
 
         self.entries.add_multiple(paths);
@@ -242,13 +503,24 @@ impl multi_window::Application for XMODITS {
     type Executor = iced::executor::Default;
     type Message = Message;
     type Theme = theme::Theme;
-    type Flags = Config;
+    type Flags = Flags;
 
     fn new(flags: Self::Flags) -> (Self, Command<Message>) {
         let mut app = Self::default();
-        app.load_cfg(flags);
+        app.baseline = Baseline::new(flags.saved, flags.config.clone());
+        let load_cfg = app.load_cfg(flags.config);
+        app.profiles = Profiles::new(flags.profile);
+        app.recovery = (!flags.recovery.dropped.is_empty()).then_some(flags.recovery);
+
+        let retry = match flags.retry_from {
+            Some(log) => Command::perform(ErrorHandler::read(log), Message::RetryFailedResult),
+            None => Command::none(),
+        };
 
-        (app, font::load().map(Message::FontLoaded))
+        (
+            app,
+            Command::batch([font::load().map(Message::FontLoaded), load_cfg, retry]),
+        )
     }
 
     fn title(&self, id: window::Id) -> String {
@@ -266,15 +538,82 @@ impl multi_window::Application for XMODITS {
         match message {
             Message::AboutPressed => self.view = View::About,
             Message::ConfigPressed => self.view = View::Configure,
+            Message::ExportPressed => self.view = View::Export,
+            Message::QueuePressed => self.view = View::Queue,
             Message::SettingsPressed => self.view = View::Settings,
             Message::Add(paths) => self.add_entries(paths),
+            Message::AddToQueue => return self.enqueue(),
+            Message::AutoSave => {
+                if self.is_dirty() {
+                    return self.save_cfg();
+                }
+            }
+            Message::ClosePrompt(save) => match save {
+                Some(true) => return self.save_and_close(),
+                Some(false) => return window::close(window::Id::MAIN),
+                None => (),
+            },
+            Message::CloseSaveFailed(e) => {
+                tracing::error!("Failed to save the configuration: {}", e);
+                return Command::perform(
+                    dialog::save_failed_before_closing(e),
+                    Message::ClosePrompt,
+                );
+            }
+            Message::Exit => return window::close(window::Id::MAIN),
+            Message::Queue(queue::Message::BrowseErrors(id)) => {
+                if let Some(job) = self.queue.get_completed(id) {
+                    let (browser, command) = ErrorBrowser::open(&job.state);
+                    self.error_browser = browser;
+                    return command.map(Message::ErrorBrowser);
+                }
+            }
+            Message::Queue(msg) => self.queue_panel.update(&mut self.queue, msg),
+            Message::Profiles(profiles::Message::Select(name)) => return self.load_profile(name),
+            Message::Profiles(profiles::Message::Save) => {
+                let profile = self.current_profile();
+                return self.profiles.save(profile).map(Message::Profiles);
+            }
+            Message::Profiles(profiles::Message::Create) => {
+                let profile = self.current_profile();
+                return self.profiles.create(profile).map(Message::Profiles);
+            }
+            Message::Profiles(msg) => return self.profiles.update(msg).map(Message::Profiles),
+            Message::Transfer(transfer::Message::Export) => {
+                let config = self.current_cfg();
+                return self.transfer.export(config).map(Message::Transfer);
+            }
+            Message::Transfer(transfer::Message::ImportResult(Some(result))) => {
+                return self.import_cfg(result)
+            }
+            Message::Transfer(msg) => return self.transfer.update(msg).map(Message::Transfer),
             Message::Clear => self.clear_entries(),
             Message::DeleteSelected => self.delete_selected_entries(),
+            Message::DismissRecovery => self.recovery = None,
             Message::InvertSelection => self.entries.invert(),
             Message::Select { index, selected } => self.entries.select(index, selected),
             Message::SelectAll(selected) => self.entries.select_all(selected),
-            Message::SetState(state) => self.state = state,
+            Message::SetState(state) => {
+                self.error_browser = ErrorBrowser::default();
+                self.state = state
+            }
             Message::About(msg) => return about::update(msg).map(Message::About),
+            Message::BrowseErrors => {
+                if let RippingState::Finished { state, .. } = &self.state {
+                    let (browser, command) = ErrorBrowser::open(state);
+                    self.error_browser = browser;
+                    return command.map(Message::ErrorBrowser);
+                }
+            }
+            Message::ErrorBrowser(error_browser::Message::Preview(path)) => {
+                return self
+                    .sample_player
+                    .create_instance(path)
+                    .map(Message::SamplePlayer)
+            }
+            Message::ErrorBrowser(msg) => {
+                return self.error_browser.update(msg).map(Message::ErrorBrowser)
+            }
             Message::FileDialog => {
                 return Command::perform(files_dialog(), Message::Add);
             }
@@ -288,6 +627,16 @@ impl multi_window::Application for XMODITS {
                 return sample_ripping::update(&mut self.ripping_cfg, msg).map(Message::RippingCfg)
             }
             Message::NamingCfg(msg) => sample_naming::update(&mut self.naming_cfg, msg),
+            Message::ConversionCfg(msg) => {
+                sample_conversion::update(&mut self.ripping_cfg.conversion, msg)
+            }
+            Message::ProcessingCfg(msg) => {
+                sample_processing::update(&mut self.ripping_cfg.processing, msg);
+                return self
+                    .sample_player
+                    .set_processing(self.ripping_cfg.processing)
+                    .map(Message::SamplePlayer);
+            }
             Message::Open(link) => {
                 if let Err(err) = open::that_detached(link) {
                     tracing::warn!("Could not open external link: {:?}", err)
@@ -321,8 +670,13 @@ impl multi_window::Application for XMODITS {
             Message::SaveConfig => {
                 return self.save_cfg();
             }
-            Message::SaveConfigResult() => {}
-            Message::SaveErrors => return self.state.export_errors(),
+            Message::SaveConfigResult(result) => match result {
+                Ok(baseline) => self.baseline = baseline,
+                Err(e) => tracing::error!("Failed to save the configuration: {}", e),
+            },
+            Message::SaveErrors => {
+                return self.state.export_errors(self.general_cfg.error_log_format)
+            }
             Message::SaveErrorsResult(result) => {
                 if let Ok(path) = result {
                     tracing::info!("Successfully saved errors to: {}", &path.display());
@@ -331,20 +685,38 @@ impl multi_window::Application for XMODITS {
                     }
                 }
             }
+            Message::RetryFailed => return self.state.retry_failed(),
+            Message::RetryFailedResult(result) => match result {
+                Ok(errors) => self.retry_failed(errors),
+                Err(error) => tracing::error!("Failed to read the error log: {}", error),
+            },
             Message::StartRipping => {
                 return self.start_ripping();
             }
             Message::Cancel => {
+                self.state.set_paused(false);
                 self.state.set_message("Cancelling...");
                 self.ripper.cancel();
             }
+            Message::Pause => {
+                self.ripper.pause();
+                self.state.set_paused(true);
+            }
+            Message::Resume => {
+                self.ripper.resume();
+                self.state.set_paused(false);
+            }
             Message::Event(event) => match event {
                 event::Event::Clear => self.clear_entries(),
                 event::Event::Closed(id) => match id != window::Id::MAIN {
                     true => self.sample_player.remove_instance(id),
                     false => return self.sample_player.close_all().map(Message::SamplePlayer),
                 },
-                event::Event::CloseRequested => {}
+                event::Event::CloseRequested(id) => {
+                    if id == window::Id::MAIN {
+                        return self.close();
+                    }
+                }
                 event::Event::Delete => self.delete_selected_entries(),
                 event::Event::FileHoveredLeft(id) => match id == window::Id::MAIN {
                     true => self.file_hovered = false,
@@ -370,16 +742,20 @@ impl multi_window::Application for XMODITS {
                 event::Event::Start => return self.start_ripping(),
             },
             Message::Subscription(msg) => match msg {
-                ripper::Message::Ready(sender) => self.ripper.set_sender(sender),
+                ripper::Message::Ready(sender) => {
+                    self.ripper.set_sender(sender);
+                    self.continue_queue();
+                }
                 ripper::Message::Info(info) => self.state.update_message(info),
-                ripper::Message::Progress { progress, errors } => {
-                    self.state.update_progress(progress, errors)
+                ripper::Message::Progress { stats, recent } => {
+                    self.state.update_progress(stats, recent)
                 }
                 ripper::Message::Done {
                     state,
                     time,
                     destination,
                 } => {
+                    self.queue.finish(&state, &time);
                     self.state = RippingState::Finished {
                         state,
                         time,
@@ -418,6 +794,12 @@ impl multi_window::Application for XMODITS {
 
         let top_left_menu = row![
             button("Ripping").on_press(Message::ConfigPressed),
+            button("Export").on_press(Message::ExportPressed),
+            button(text(match self.queue.len() {
+                0 => "Queue".to_owned(),
+                n => format!("Queue ({})", n),
+            }))
+            .on_press(Message::QueuePressed),
             button("Settings").on_press(Message::SettingsPressed),
             button("About").on_press(Message::AboutPressed),
         ]
@@ -427,11 +809,20 @@ impl multi_window::Application for XMODITS {
 
         let not_ripping = !self.state.is_ripping();
 
+        let save_label = match self.is_dirty() {
+            true => "Save Settings*",
+            false => "Save Settings",
+        };
+
         let bottom_left_buttons = row![
-            button(text_icon("Save Settings", icon::save()))
+            button(text_icon(save_label, icon::save()))
                 .on_press(Message::SaveConfig)
                 .width(Length::FillPortion(2))
                 .padding(8),
+            button("Add to Queue")
+                .on_press_maybe((!self.entries.is_empty()).then_some(Message::AddToQueue))
+                .width(Length::FillPortion(2))
+                .padding(8),
             button(text_icon("START", icon::download()))
                 .on_press_maybe(not_ripping.then_some(Message::StartRipping))
                 .style(theme::Button::Start)
@@ -456,6 +847,7 @@ impl multi_window::Application for XMODITS {
 
                 column![
                     self.tracker_info.view(),
+                    self.profiles.view_picker().map(Message::Profiles),
                     naming_cfg,
                     ripping_cfg,
                     bottom_left_buttons,
@@ -463,7 +855,53 @@ impl multi_window::Application for XMODITS {
                 .spacing(10)
                 .into()
             }
-            View::Settings => settings::view(&self.general_cfg).map(Message::GeneralCfg),
+            View::Export => {
+                let processing_cfg = sample_processing::view(&self.ripping_cfg.processing)
+                    .map(Message::ProcessingCfg);
+
+                let conversion_cfg = sample_conversion::view(&self.ripping_cfg.conversion)
+                    .map(Message::ConversionCfg);
+
+                let metadata_cfg =
+                    sample_ripping::view_metadata(&self.ripping_cfg).map(Message::RippingCfg);
+
+                let instruments_cfg =
+                    sample_ripping::view_instruments(&self.ripping_cfg).map(Message::RippingCfg);
+
+                let filters_cfg =
+                    sample_ripping::view_filters(&self.ripping_cfg).map(Message::RippingCfg);
+
+                scrollable(
+                    column![
+                        filters_cfg,
+                        processing_cfg,
+                        conversion_cfg,
+                        metadata_cfg,
+                        instruments_cfg
+                    ]
+                    .spacing(10),
+                )
+                .into()
+            }
+            View::Queue => column![
+                self.queue_panel.view(&self.queue).map(Message::Queue),
+                Space::with_height(Length::Fill),
+                bottom_left_buttons
+            ]
+            .spacing(10)
+            .into(),
+            View::Settings => scrollable(
+                column![]
+                    .push_maybe(warning(
+                        || self.is_dirty(),
+                        "You have unsaved changes. Press \"Save Settings\" or CTRL + S to keep them.",
+                    ))
+                    .push(settings::view(&self.general_cfg).map(Message::GeneralCfg))
+                    .push(self.profiles.view().map(Message::Profiles))
+                    .push(self.transfer.view().map(Message::Transfer))
+                    .spacing(8),
+            )
+            .into(),
             View::About => about::view().map(Message::About),
         };
 
@@ -490,22 +928,19 @@ impl multi_window::Application for XMODITS {
         .align_items(Alignment::Center);
 
         let bottom_right_buttons = row![
-            action("Add File", not_ripping.then_some(Message::FileDialog)).padding(8),
-            action("Add Folder", not_ripping.then_some(Message::FolderDialog)).padding(8),
+            action("Add File", Some(Message::FileDialog)).padding(8),
+            action("Add Folder", Some(Message::FolderDialog)).padding(8),
             Space::with_width(Length::Fill)
         ]
         .push_maybe(
             (self.entries.total_selected() > 0 && (!self.entries.all_selected())).then(|| {
-                action(
-                    "Clear Selected",
-                    not_ripping.then_some(Message::DeleteSelected),
-                )
-                .padding(8)
-                .style(theme::Button::Cancel)
+                action("Clear Selected", Some(Message::DeleteSelected))
+                    .padding(8)
+                    .style(theme::Button::Cancel)
             }),
         )
         .push(
-            action("Clear", not_ripping.then_some(Message::Clear))
+            action("Clear", Some(Message::Clear))
                 .padding(8)
                 .style(theme::Button::Cancel),
         )
@@ -513,12 +948,16 @@ impl multi_window::Application for XMODITS {
 
         let show_gif = !self.general_cfg.hide_gif;
         let main_view = match &self.state {
+            _ if !self.state.is_ripping() && self.error_browser.is_open() => {
+                self.error_browser.view().map(Message::ErrorBrowser)
+            }
             RippingState::Idle => self.entries.view(self.file_hovered, show_gif),
             RippingState::Ripping {
                 message,
-                progress,
-                errors,
-            } => ripping::view_ripping(message, *progress, *errors, show_gif),
+                stats,
+                feed,
+                paused,
+            } => ripping::view_ripping(message, stats, feed, *paused, show_gif),
             RippingState::Finished {
                 state,
                 time,
@@ -539,7 +978,21 @@ impl multi_window::Application for XMODITS {
             "That's a lot of files! You REALLY should be using folders.",
         );
 
+        let recovery_warning = self.recovery.as_ref().map(|recovery| {
+            row![
+                text(recovery)
+                    .style(theme::Text::Warning)
+                    .width(Length::Fill),
+                button("Dismiss")
+                    .on_press(Message::DismissRecovery)
+                    .style(theme::Button::Hyperlink),
+            ]
+            .spacing(8)
+            .align_items(Alignment::Center)
+        });
+
         let right_half = column![destination, top_right_buttons, main_view]
+            .push_maybe(recovery_warning)
             .push_maybe(bad_cfg_warning)
             .push_maybe(too_many_files_warning)
             .push(bottom_right_buttons)
@@ -556,10 +1009,16 @@ impl multi_window::Application for XMODITS {
     }
 
     fn subscription(&self) -> Subscription<Message> {
+        let auto_save = match self.general_cfg.auto_save {
+            true => iced::time::every(AUTO_SAVE_INTERVAL).map(|_| Message::AutoSave),
+            false => Subscription::none(),
+        };
+
         iced::Subscription::batch([
             event::events().map(Message::Event),
             ripper::subscription().map(Message::Subscription),
             crash::subscription().map(Message::Crashes),
+            auto_save,
         ])
     }
 }
@@ -1,16 +1,20 @@
 use data::config::Config;
 
+use crate::cli::Overrides;
 use crate::dialog;
-use crate::logger::write_error_log;
-use crate::ripper::subscription::extraction::strict_loading;
+use crate::ripper::extraction::error::Reason;
+use crate::ripper::extraction::error_handler;
+use crate::ripper::progress::{Counters, Meter};
+use crate::ripper::subscription::extraction::{strict_loading, ModuleRipper};
+use crate::ripper::subscription::{CompleteState, ErrorHandler, Failed};
 
-use std::cmp::Ordering;
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-use xmodits_lib::interface::Error;
-use xmodits_lib::{common::extract, interface::ripper::Ripper};
+/// How often the progress is printed
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
 
-pub fn rip(paths: impl IntoIterator<Item = String>) {
+pub fn rip(paths: impl IntoIterator<Item = String>, overrides: Overrides) {
     let mut paths: Vec<PathBuf> = paths
         .into_iter()
         .map(PathBuf::from)
@@ -27,7 +31,8 @@ pub fn rip(paths: impl IntoIterator<Item = String>) {
         return dialog::show_help_box();
     };
 
-    let config = Config::load();
+    let mut config = Config::load();
+    overrides.apply(&mut config);
 
     let filter = strict_loading(config.ripping.strict);
 
@@ -49,35 +54,75 @@ pub fn rip(paths: impl IntoIterator<Item = String>) {
         },
     };
 
-    let log_path = config.general.logging_path.as_ref().unwrap_or(&destination);
+    let log_dir = error_handler::log_dir(config.general.logging_path.as_deref(), &destination);
+    let error_log_format = config.general.error_log_format;
 
-    let self_contained = config.ripping.self_contained;
+    let quiet_output = config.general.non_gui_quiet_output;
 
-    let mut ripper = Ripper::default();
-    ripper.change_namer(config.naming.build_func());
-    ripper.change_format(config.ripping.exported_format.get_impl());
+    let ripper = ModuleRipper::new(&config.ripping, &config.naming);
+    let mut errors = ErrorHandler::new(log_dir.clone(), error_log_format);
 
-    let errors: Vec<(PathBuf, Error)> = paths
-        .into_iter()
-        .filter_map(|mod_path| {
-            extract(&mod_path, &destination, &ripper, self_contained)
-                .err()
-                .map(|error| (mod_path, error))
-        })
-        .collect();
+    let counters = Counters::new();
+    counters.set_total(paths.len() as u64);
+    let mut meter = Meter::new(counters.clone());
+    let mut last_report = Instant::now();
 
-    let quiet_output = config.general.non_gui_quiet_output;
+    // The error handler writes to the log asynchronously, same as the GUI.
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .expect("Building runtime for the error handler.");
+
+    runtime.block_on(async {
+        for mod_path in paths {
+            let (ripped, result) = ripper.rip_counted(&mod_path, &destination);
+            let failed = result
+                .err()
+                .map(|error| Failed::new(mod_path.display().to_string(), error));
+
+            counters.push(&mod_path, &ripped, failed.as_ref());
+
+            if let Some(failed) = failed {
+                errors.push(failed).await;
+            }
+
+            let stats = meter.stats();
+
+            if !quiet_output && last_report.elapsed() >= REPORT_INTERVAL {
+                last_report = Instant::now();
+                println!(
+                    "Ripped {}% - {} - {}",
+                    stats.percentage().floor(),
+                    stats.throughput(),
+                    stats.timing()
+                );
+            }
+        }
+    });
+
+    if !quiet_output {
+        let stats = meter.stats();
+        println!("Done - {} - {}", stats.throughput(), stats.timing());
+    }
 
-    match errors.len().cmp(&1) {
-        Ordering::Less if !quiet_output => dialog::success(&destination),
-        Ordering::Equal if !quiet_output => dialog::failed_single({
-            let (_, error) = &errors[0];
-            &error.to_string()
-        }),
-        Ordering::Greater => match write_error_log(log_path, errors) {
-            Ok(log_path) => dialog::success_partial(destination, log_path),
-            Err(error) => dialog::success_partial_no_log(&error.to_string()),
+    match CompleteState::from(errors) {
+        CompleteState::NoErrors if !quiet_output => dialog::success(&destination),
+        CompleteState::SomeErrors(errors) => match errors.as_slice() {
+            [Failed {
+                reason: Reason::Single(reason),
+                ..
+            }] if !quiet_output => dialog::failed_single(reason),
+            [_] if quiet_output => (),
+            _ => {
+                let log_path = log_dir.join(error_handler::random_name(error_log_format));
+
+                match runtime.block_on(ErrorHandler::dump(errors, log_path, error_log_format)) {
+                    Ok(log_path) => dialog::success_partial(destination, log_path),
+                    Err(error) => dialog::success_partial_no_log(&error),
+                }
+            }
         },
+        CompleteState::TooMuchErrors { log, .. } => dialog::success_partial(destination, log),
+        CompleteState::TooMuchErrorsNoLog { reason, .. } => dialog::success_partial_no_log(&reason),
         _ => (),
     }
 }
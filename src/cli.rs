@@ -1,27 +1,86 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 
+use data::config::sample_ripping::ExportFormat;
+use data::config::Profile;
+use data::Config;
+
 static HELP: &str = "\
 --help      -h      Prints help information
 --version   -V      Prints version
+--format    -f      Sets the export format (wav, aiff, flac, its, s3i, 8svx, raw)
+--profile   -p      Uses the ripping and naming settings of a saved profile
+--retry-from <log>  Opens the modules listed in an error log so they can be ripped again
 ";
 
+/// Options that override the saved configuration
 #[derive(Debug, Default)]
+pub struct Overrides {
+    /// Applied before the other overrides
+    pub profile: Option<String>,
+    pub format: Option<ExportFormat>,
+    /// Error log of the modules to rip again
+    pub retry_from: Option<PathBuf>,
+}
+
+impl Overrides {
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(name) = &self.profile {
+            match Profile::load(name) {
+                Ok(profile) => profile.apply(config),
+                Err(e) => tracing::error!("Could not load profile \"{}\": {}", name, e),
+            }
+        }
+
+        if let Some(format) = self.format {
+            config.ripping.exported_format = format;
+        }
+    }
+}
+
+#[derive(Debug)]
 pub enum Mode {
-    #[default]
-    None,
+    None(Overrides),
     Version,
     Help,
     #[cfg(feature = "built")]
     BuildInfo,
     #[cfg(windows)]
-    DragNDrop(Vec<String>),
+    DragNDrop(Vec<String>, Overrides),
     Unrecognised(String),
+    InvalidFormat(String),
+    InvalidLog(String),
+    /// Modules can't be given along with ``--retry-from``
+    RetryWithPaths,
+    InvalidProfile(String),
     #[cfg(feature = "manual")]
     Manual,
 }
 
-pub fn parse(args: Vec<String>) -> Mode {
+pub fn parse(mut args: Vec<String>) -> Mode {
+    let mut overrides = Overrides::default();
+
+    if let Some(format) = take_value(&mut args, ["--format", "-f"]) {
+        match format.parse() {
+            Ok(format) => overrides.format = Some(format),
+            Err(_) => return Mode::InvalidFormat(format),
+        }
+    }
+
+    if let Some(name) = take_value(&mut args, ["--profile", "-p"]) {
+        match Profile::exists(&name) {
+            true => overrides.profile = Some(name),
+            false => return Mode::InvalidProfile(name),
+        }
+    }
+
+    if let Some(log) = take_value(&mut args, ["--retry-from"]) {
+        match Path::new(&log).is_file() {
+            true => overrides.retry_from = Some(log.into()),
+            false => return Mode::InvalidLog(log),
+        }
+    }
+
     if contains(&args, ["--help", "-h"]) {
         return Mode::Help;
     }
@@ -47,12 +106,16 @@ pub fn parse(args: Vec<String>) -> Mode {
         return Mode::Unrecognised(unrecognised.to_owned());
     }
 
+    if !args.is_empty() && overrides.retry_from.is_some() {
+        return Mode::RetryWithPaths;
+    }
+
     #[cfg(windows)]
     if !args.is_empty() {
-        return Mode::DragNDrop(args);
+        return Mode::DragNDrop(args, overrides);
     }
 
-    Mode::None
+    Mode::None(overrides)
 }
 
 pub fn print_help() -> ! {
@@ -86,12 +149,49 @@ pub fn print_unrecognised(option: String) -> ! {
     print_help()
 }
 
+pub fn print_invalid_format(format: String) -> ! {
+    eprintln!("Unsupported format '{format}'");
+    print_help()
+}
+
+pub fn print_invalid_log(log: String) -> ! {
+    eprintln!("Couldn't find the error log '{log}'");
+    print_help()
+}
+
+pub fn print_retry_with_paths() -> ! {
+    eprintln!("--retry-from can't be used with other modules, rip them separately");
+    print_help()
+}
+
+pub fn print_invalid_profile(name: String) -> ! {
+    eprintln!("Couldn't find the profile '{name}'");
+
+    match Profile::list().as_slice() {
+        [] => eprintln!("There are no saved profiles"),
+        names => eprintln!("Saved profiles: {}", names.join(", ")),
+    }
+
+    exit(1)
+}
+
 #[cfg(feature = "manual")]
 pub fn print_manual() -> ! {
     print!("{}", data::MANUAL);
     exit(0);
 }
 
+/// Remove the flag and the value that follows it, returning the value.
+fn take_value<const T: usize>(args: &mut Vec<String>, flags: [&str; T]) -> Option<String> {
+    let index = args.iter().position(|f| flags.contains(&f.as_str()))?;
+    args.remove(index);
+
+    match index < args.len() {
+        true => Some(args.remove(index)),
+        false => Some(String::new()),
+    }
+}
+
 fn contains<const T: usize>(args: &[String], flags: [&str; T]) -> bool {
     args.iter().any(|f| flags.contains(&f.as_str()))
 }
@@ -86,3 +86,40 @@ pub fn no_valid_modules() {
 pub fn critical_error(error: &str) {
     show_dialog("FATAL ERROR (>_<)", error, MessageLevel::Error).show();
 }
+
+/// Ask whether the settings should be saved before closing.
+///
+/// Returns `None` if the user wants to keep the application open.
+pub async fn save_before_closing() -> Option<bool> {
+    let dialog = rfd::AsyncMessageDialog::new()
+        .set_title("Unsaved changes")
+        .set_description(
+            "Your settings have changed since they were last saved.\n\nSave them before closing?",
+        )
+        .set_level(MessageLevel::Warning)
+        .set_buttons(MessageButtons::YesNoCancel);
+
+    match dialog.show().await {
+        MessageDialogResult::Yes => Some(true),
+        MessageDialogResult::No => Some(false),
+        _ => None,
+    }
+}
+
+/// Saving the settings failed while closing, ask whether to close anyway.
+///
+/// Returns `Some(false)` to close without saving, or `None` to keep the application open.
+pub async fn save_failed_before_closing(error: String) -> Option<bool> {
+    let dialog = rfd::AsyncMessageDialog::new()
+        .set_title("Couldn't save settings")
+        .set_description(format!(
+            "Your settings couldn't be saved:\n{error}\n\nClose anyway? Your changes will be lost."
+        ))
+        .set_level(MessageLevel::Error)
+        .set_buttons(MessageButtons::YesNo);
+
+    match dialog.show().await {
+        MessageDialogResult::Yes => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,163 @@
+//! Digital signal processing applied to samples before they're exported.
+//!
+//! Buffers are non-interleaved, i.e. one ``Vec<f32>`` per channel.
+
+pub mod dither;
+pub mod noise;
+pub mod resample;
+
+use std::ops::Range;
+
+use data::config::sample_processing::{Normalize, SampleProcessingConfig};
+
+/// Run the processing chain over the buffer.
+///
+/// Returns the range of frames (relative to the original buffer) that were kept,
+/// so that loop points can be adjusted accordingly.
+pub fn process(buf: &mut [Vec<f32>], rate: u32, cfg: &SampleProcessingConfig) -> Range<usize> {
+    if cfg.remove_dc_offset {
+        remove_dc_offset(buf);
+    }
+
+    let kept = match cfg.trim_silence {
+        true => trim_silence(buf, db_to_amplitude(cfg.silence_threshold)),
+        false => 0..frames(buf),
+    };
+
+    let target = db_to_amplitude(cfg.normalize_target);
+
+    match cfg.normalize {
+        Normalize::Off => (),
+        Normalize::Peak => normalize_peak(buf, target),
+        Normalize::Rms => normalize_rms(buf, target),
+    }
+
+    fade_in(buf, ms_to_frames(cfg.fade_in, rate));
+    fade_out(buf, ms_to_frames(cfg.fade_out, rate));
+
+    kept
+}
+
+/// Mix every channel down to a single channel
+pub fn downmix(buf: &mut Vec<Vec<f32>>) {
+    if buf.len() < 2 {
+        return;
+    }
+
+    let channels = buf.len() as f32;
+    let mixed = (0..frames(buf))
+        .map(|frame| buf.iter().map(|c| c[frame]).sum::<f32>() / channels)
+        .collect();
+
+    *buf = vec![mixed];
+}
+
+/// Copy a mono channel to both sides
+pub fn duplicate(buf: &mut Vec<Vec<f32>>) {
+    if let [mono] = buf.as_slice() {
+        buf.push(mono.clone());
+    }
+}
+
+pub fn frames(buf: &[Vec<f32>]) -> usize {
+    buf.first().map(Vec::len).unwrap_or_default()
+}
+
+pub fn db_to_amplitude(db: i8) -> f32 {
+    10_f32.powf(db as f32 / 20.0)
+}
+
+fn ms_to_frames(ms: u16, rate: u32) -> usize {
+    (rate as u64 * ms as u64 / 1000) as usize
+}
+
+/// Subtract the mean of each channel
+pub fn remove_dc_offset(buf: &mut [Vec<f32>]) {
+    for channel in buf.iter_mut().filter(|c| !c.is_empty()) {
+        let mean = (channel.iter().map(|s| *s as f64).sum::<f64>() / channel.len() as f64) as f32;
+        channel.iter_mut().for_each(|s| *s -= mean);
+    }
+}
+
+/// Remove leading and trailing frames that stay below the threshold on every channel.
+///
+/// A sample that is entirely silent is left untouched.
+pub fn trim_silence(buf: &mut [Vec<f32>], threshold: f32) -> Range<usize> {
+    let total = frames(buf);
+    let is_loud = |frame: usize| buf.iter().any(|channel| channel[frame].abs() > threshold);
+
+    let Some(start) = (0..total).find(|frame| is_loud(*frame)) else {
+        return 0..total;
+    };
+
+    // There's at least one loud frame, so this will always succeed.
+    let end = (0..total).rfind(|frame| is_loud(*frame)).unwrap_or(start) + 1;
+
+    for channel in buf.iter_mut() {
+        channel.truncate(end);
+        channel.drain(..start);
+    }
+
+    start..end
+}
+
+pub fn peak(buf: &[Vec<f32>]) -> f32 {
+    buf.iter()
+        .flatten()
+        .fold(0.0, |peak: f32, sample| peak.max(sample.abs()))
+}
+
+pub fn rms(buf: &[Vec<f32>]) -> f32 {
+    let total = buf.iter().map(Vec::len).sum::<usize>();
+
+    if total == 0 {
+        return 0.0;
+    }
+
+    let sum: f64 = buf.iter().flatten().map(|s| (*s as f64).powi(2)).sum();
+    (sum / total as f64).sqrt() as f32
+}
+
+pub fn normalize_peak(buf: &mut [Vec<f32>], target: f32) {
+    let peak = peak(buf);
+
+    if peak > 0.0 {
+        apply_gain(buf, target / peak);
+    }
+}
+
+/// Normalize the loudness of the sample.
+///
+/// The gain is limited so that the sample won't clip.
+pub fn normalize_rms(buf: &mut [Vec<f32>], target: f32) {
+    let rms = rms(buf);
+    let peak = peak(buf);
+
+    if rms > 0.0 && peak > 0.0 {
+        apply_gain(buf, (target / rms).min(1.0 / peak));
+    }
+}
+
+fn apply_gain(buf: &mut [Vec<f32>], gain: f32) {
+    buf.iter_mut().flatten().for_each(|sample| *sample *= gain);
+}
+
+pub fn fade_in(buf: &mut [Vec<f32>], length: usize) {
+    for channel in buf.iter_mut() {
+        let length = length.min(channel.len());
+
+        for (i, sample) in channel.iter_mut().take(length).enumerate() {
+            *sample *= i as f32 / length as f32;
+        }
+    }
+}
+
+pub fn fade_out(buf: &mut [Vec<f32>], length: usize) {
+    for channel in buf.iter_mut() {
+        let length = length.min(channel.len());
+
+        for (i, sample) in channel.iter_mut().rev().take(length).enumerate() {
+            *sample *= i as f32 / length as f32;
+        }
+    }
+}
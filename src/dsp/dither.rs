@@ -0,0 +1,17 @@
+//! Quantization to integer bit depths
+
+use rand::Rng;
+
+/// Quantize a sample to a signed integer of the given bit depth.
+///
+/// If ``dither`` is set, triangular (TPDF) noise of 1 LSB is added before rounding.
+pub fn quantize(sample: f32, bits: u8, dither: bool, rng: &mut impl Rng) -> i32 {
+    let max = ((1_i64 << (bits - 1)) - 1) as f64;
+    let mut sample = sample as f64 * max;
+
+    if dither {
+        sample += rng.gen::<f64>() - rng.gen::<f64>();
+    }
+
+    sample.round().clamp(-max - 1.0, max) as i32
+}
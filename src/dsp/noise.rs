@@ -0,0 +1,318 @@
+//! Detect samples that are most likely static noise.
+//!
+//! Almost any file can be loaded as a MOD, and the "samples" it produces are just the raw bytes
+//! of the file: loud, full scale noise. Real samples, even noisy ones like hi-hats and snares,
+//! have a spectrum with some shape, cross zero less often, and spend most of their time
+//! near silence. Garbage has a flat spectrum and uses every amplitude equally.
+
+use std::f32::consts::PI;
+
+/// Samples that score at least this much are considered noise.
+pub const THRESHOLD: f32 = 0.8;
+
+/// Size of the windows used to measure the spectrum, must be a power of two.
+const WINDOW: usize = 1024;
+
+/// Only a handful of windows are needed to get a good idea of the spectrum.
+const MAX_WINDOWS: usize = 16;
+
+/// Samples shorter than this don't have enough information to analyse.
+const MIN_FRAMES: usize = 64;
+
+/// Anything louder than this is considered clipped.
+const CLIP_LEVEL: f32 = 0.99;
+
+const HISTOGRAM_BINS: usize = 64;
+
+/// Heuristics used to decide if a sample is noise.
+///
+/// Every measurement is between 0.0 and 1.0.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Analysis {
+    /// How flat the spectrum is, white noise is around 0.56.
+    pub flatness: f32,
+    /// How often the signal changes sign, white noise is around 0.5.
+    pub zero_crossing_rate: f32,
+    /// Ratio of samples that are at full scale.
+    pub clipping: f32,
+    /// Entropy of the amplitude distribution, 1.0 if every amplitude is equally likely.
+    pub entropy: f32,
+}
+
+impl Analysis {
+    /// Combine the measurements into a single score between 0.0 and 1.0.
+    pub fn score(&self) -> f32 {
+        let flatness = (self.flatness / 0.5).min(1.0);
+        let zero_crossing_rate = (self.zero_crossing_rate / 0.5).min(1.0);
+        let clipping = (self.clipping / 0.01).min(1.0);
+
+        0.3 * flatness + 0.2 * zero_crossing_rate + 0.15 * clipping + 0.35 * self.entropy
+    }
+
+    pub fn is_noise(&self) -> bool {
+        self.score() >= THRESHOLD
+    }
+}
+
+impl std::fmt::Display for Analysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "score {:.2}: spectral flatness {:.2}, zero crossing rate {:.2}, clipping {:.1}%, entropy {:.2}",
+            self.score(),
+            self.flatness,
+            self.zero_crossing_rate,
+            self.clipping * 100.0,
+            self.entropy
+        )
+    }
+}
+
+/// Analyse a non-interleaved buffer.
+pub fn analyse(buf: &[Vec<f32>]) -> Analysis {
+    let mono = mix(buf);
+
+    if mono.len() < MIN_FRAMES || mono.iter().all(|s| *s == 0.0) {
+        return Analysis::default();
+    }
+
+    Analysis {
+        flatness: spectral_flatness(&mono),
+        zero_crossing_rate: zero_crossing_rate(&mono),
+        clipping: clipping(&mono),
+        entropy: entropy(&mono),
+    }
+}
+
+fn mix(buf: &[Vec<f32>]) -> Vec<f32> {
+    match buf {
+        [] => Vec::new(),
+        [mono] => mono.clone(),
+        channels => {
+            let frames = super::frames(channels);
+            (0..frames)
+                .map(|i| channels.iter().map(|c| c[i]).sum::<f32>() / channels.len() as f32)
+                .collect()
+        }
+    }
+}
+
+fn zero_crossing_rate(samples: &[f32]) -> f32 {
+    let crossings = samples
+        .windows(2)
+        .filter(|pair| (pair[0] < 0.0) != (pair[1] < 0.0))
+        .count();
+
+    crossings as f32 / (samples.len() - 1) as f32
+}
+
+fn clipping(samples: &[f32]) -> f32 {
+    let clipped = samples.iter().filter(|s| s.abs() >= CLIP_LEVEL).count();
+    clipped as f32 / samples.len() as f32
+}
+
+/// Shannon entropy of the amplitude histogram, normalized.
+fn entropy(samples: &[f32]) -> f32 {
+    let mut histogram = [0_usize; HISTOGRAM_BINS];
+
+    for sample in samples {
+        let bin = ((sample.clamp(-1.0, 1.0) + 1.0) * 0.5 * HISTOGRAM_BINS as f32) as usize;
+        histogram[bin.min(HISTOGRAM_BINS - 1)] += 1;
+    }
+
+    let total = samples.len() as f32;
+
+    let entropy: f32 = histogram
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f32 / total;
+            -p * p.log2()
+        })
+        .sum();
+
+    entropy / (HISTOGRAM_BINS as f32).log2()
+}
+
+/// Average spectral flatness of evenly spaced windows.
+///
+/// Samples shorter than a window are zero padded, silent windows are ignored.
+fn spectral_flatness(samples: &[f32]) -> f32 {
+    let windows = (samples.len() / WINDOW).clamp(1, MAX_WINDOWS);
+    let step = match windows {
+        1 => 0,
+        n => (samples.len() - WINDOW) / (n - 1),
+    };
+
+    let hann: Vec<f32> = (0..WINDOW)
+        .map(|i| 0.5 - 0.5 * (2.0 * PI * i as f32 / WINDOW as f32).cos())
+        .collect();
+
+    let mut total = 0.0;
+    let mut measured = 0;
+
+    for window in 0..windows {
+        let start = window * step;
+        let mut re = vec![0.0; WINDOW];
+        let mut im = vec![0.0; WINDOW];
+
+        samples
+            .iter()
+            .skip(start)
+            .take(WINDOW)
+            .zip(&hann)
+            .enumerate()
+            .for_each(|(i, (s, w))| re[i] = s * w);
+
+        fft(&mut re, &mut im);
+
+        // Skip the DC bin
+        let power: Vec<f32> = (1..WINDOW / 2)
+            .map(|i| re[i] * re[i] + im[i] * im[i])
+            .collect();
+
+        let arithmetic = power.iter().sum::<f32>() / power.len() as f32;
+
+        if arithmetic <= f32::EPSILON {
+            continue;
+        }
+
+        let geometric =
+            (power.iter().map(|p| (p + 1e-12).ln()).sum::<f32>() / power.len() as f32).exp();
+
+        total += geometric / arithmetic;
+        measured += 1;
+    }
+
+    match measured {
+        0 => 0.0,
+        n => (total / n as f32).min(1.0),
+    }
+}
+
+/// In place radix-2 FFT
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    let bits = n.trailing_zeros();
+
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut size = 2;
+
+    while size <= n {
+        let angle = -2.0 * PI / size as f32;
+
+        for start in (0..n).step_by(size) {
+            for k in 0..size / 2 {
+                let (sin, cos) = (angle * k as f32).sin_cos();
+                let a = start + k;
+                let b = a + size / 2;
+
+                let t_re = re[b] * cos - im[b] * sin;
+                let t_im = re[b] * sin + im[b] * cos;
+
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+            }
+        }
+
+        size *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAMES: usize = 8192;
+
+    /// Uniform white noise between -amplitude and amplitude.
+    fn white_noise(frames: usize, amplitude: f32) -> Vec<f32> {
+        let mut seed = 0x1234_5678_u32;
+
+        (0..frames)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                ((seed >> 8) as f32 / (1 << 24) as f32 * 2.0 - 1.0) * amplitude
+            })
+            .collect()
+    }
+
+    /// A 440 Hz sine wave at 22050 Hz
+    fn sine(frames: usize) -> Vec<f32> {
+        (0..frames)
+            .map(|i| (i as f32 * 440.0 / 22050.0 * 2.0 * PI).sin() * 0.8)
+            .collect()
+    }
+
+    #[test]
+    fn white_noise_is_noise() {
+        let analysis = analyse(&[white_noise(FRAMES, 1.0)]);
+
+        assert!((0.5..0.62).contains(&analysis.flatness), "{analysis}");
+        assert!(
+            (0.45..0.55).contains(&analysis.zero_crossing_rate),
+            "{analysis}"
+        );
+        assert!(analysis.clipping >= 0.005, "{analysis}");
+        assert!(analysis.entropy > 0.95, "{analysis}");
+        assert!(analysis.score() > 0.95, "{analysis}");
+        assert!(analysis.is_noise());
+    }
+
+    #[test]
+    fn quiet_noise_is_not_noise() {
+        // Like a hi-hat, which stays near silence most of the time
+        let analysis = analyse(&[white_noise(FRAMES, 0.1)]);
+
+        assert!(analysis.entropy < 0.5, "{analysis}");
+        assert!(analysis.score() < THRESHOLD, "{analysis}");
+        assert!(!analysis.is_noise());
+    }
+
+    #[test]
+    fn sine_is_not_noise() {
+        let analysis = analyse(&[sine(FRAMES)]);
+
+        assert!(analysis.flatness < 0.05, "{analysis}");
+        assert!(analysis.zero_crossing_rate < 0.05, "{analysis}");
+        assert_eq!(analysis.clipping, 0.0);
+        assert!(analysis.score() < 0.5, "{analysis}");
+        assert!(!analysis.is_noise());
+    }
+
+    #[test]
+    fn silence_is_not_noise() {
+        let analysis = analyse(&[vec![0.0; FRAMES]]);
+
+        assert_eq!(analysis, Analysis::default());
+        assert!(!analysis.is_noise());
+    }
+
+    #[test]
+    fn short_samples_are_not_analysed() {
+        let analysis = analyse(&[white_noise(MIN_FRAMES - 1, 1.0)]);
+
+        assert_eq!(analysis, Analysis::default());
+    }
+
+    #[test]
+    fn stereo_is_mixed_down() {
+        let noise = white_noise(FRAMES, 1.0);
+        let inverted = noise.iter().map(|s| -s).collect();
+
+        // The channels cancel out
+        let analysis = analyse(&[noise.clone(), inverted]);
+        assert_eq!(analysis, Analysis::default());
+
+        assert!(analyse(&[noise.clone(), noise]).is_noise());
+    }
+}
@@ -0,0 +1,106 @@
+//! Sample rate conversion
+
+use data::config::sample_conversion::Resampler;
+
+/// Half the number of taps used by the windowed sinc resampler
+const SINC_HALF_WIDTH: f64 = 16.0;
+
+/// Resample every channel of the buffer.
+pub fn resample(buf: &mut [Vec<f32>], from: u32, to: u32, resampler: Resampler) {
+    if from == to || from == 0 || to == 0 {
+        return;
+    }
+
+    for channel in buf.iter_mut() {
+        *channel = resample_channel(channel, from, to, resampler);
+    }
+}
+
+/// Convert a frame position to the new sample rate
+pub fn convert_position(position: u32, from: u32, to: u32) -> u32 {
+    match from {
+        0 => position,
+        from => (position as u64 * to as u64 / from as u64) as u32,
+    }
+}
+
+fn resample_channel(input: &[f32], from: u32, to: u32, resampler: Resampler) -> Vec<f32> {
+    let step = from as f64 / to as f64;
+    let length = (input.len() as f64 / step).ceil() as usize;
+
+    let interpolate = match resampler {
+        Resampler::Linear => linear,
+        Resampler::Cubic => cubic,
+        Resampler::Sinc => sinc,
+    };
+
+    (0..length)
+        .map(|frame| interpolate(input, frame as f64 * step, step))
+        .collect()
+}
+
+/// Returns the sample at the given index, or silence if it's out of bounds.
+fn get(input: &[f32], index: isize) -> f32 {
+    match index {
+        i if i < 0 => 0.0,
+        i => input.get(i as usize).copied().unwrap_or_default(),
+    }
+}
+
+fn linear(input: &[f32], position: f64, _: f64) -> f32 {
+    let index = position.floor() as isize;
+    let frac = (position - index as f64) as f32;
+
+    let a = get(input, index);
+    let b = get(input, index + 1);
+
+    a + (b - a) * frac
+}
+
+/// Catmull-Rom spline interpolation
+fn cubic(input: &[f32], position: f64, _: f64) -> f32 {
+    let index = position.floor() as isize;
+    let t = (position - index as f64) as f32;
+
+    let y0 = get(input, index - 1);
+    let y1 = get(input, index);
+    let y2 = get(input, index + 1);
+    let y3 = get(input, index + 2);
+
+    let a = -0.5 * y0 + 1.5 * y1 - 1.5 * y2 + 0.5 * y3;
+    let b = y0 - 2.5 * y1 + 2.0 * y2 - 0.5 * y3;
+    let c = -0.5 * y0 + 0.5 * y2;
+
+    ((a * t + b) * t + c) * t + y1
+}
+
+/// Blackman windowed sinc interpolation.
+///
+/// When downsampling, the cutoff is lowered to prevent aliasing.
+fn sinc(input: &[f32], position: f64, step: f64) -> f32 {
+    use std::f64::consts::PI;
+
+    let cutoff = (1.0 / step).min(1.0);
+    let half_width = SINC_HALF_WIDTH / cutoff;
+
+    let first = (position - half_width).ceil() as isize;
+    let last = (position + half_width).floor() as isize;
+
+    let mut sum = 0.0;
+
+    for index in first..=last {
+        let x = index as f64 - position;
+
+        let sinc = match x * cutoff {
+            x if x.abs() < 1e-9 => 1.0,
+            x => (PI * x).sin() / (PI * x),
+        };
+
+        let n = (x / half_width + 1.0) / 2.0;
+        let window = 0.42 - 0.5 * (2.0 * PI * n).cos() + 0.08 * (4.0 * PI * n).cos();
+
+        sum += get(input, index) as f64 * sinc * window * cutoff;
+    }
+
+    sum as f32
+}
@@ -7,7 +7,7 @@ use std::path::PathBuf;
 #[derive(Debug, Clone)]
 pub enum Event {
     Clear,
-    CloseRequested,
+    CloseRequested(window::Id),
     Delete,
     FileDropped(window::Id, PathBuf),
     FileHovered(window::Id, PathBuf),
@@ -46,7 +46,7 @@ pub fn filter(event: iced::Event, status: event::Status) -> Option<Event> {
             window::Event::FileDropped(file) if ignored(status) => {
                 Some(Event::FileDropped(id, file))
             }
-            window::Event::CloseRequested => Some(Event::CloseRequested),
+            window::Event::CloseRequested => Some(Event::CloseRequested(id)),
             window::Event::FileHovered(path) => Some(Event::FileHovered(id, path)),
             window::Event::FilesHoveredLeft => Some(Event::FileHoveredLeft(id)),
             window::Event::Closed => Some(Event::Closed(id)),
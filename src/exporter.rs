@@ -0,0 +1,502 @@
+//! Exporters used when ripping samples.
+//!
+//! Wraps xmodits' own exporters so that samples can be processed and converted before they're written.
+
+pub mod aiff;
+pub mod chunk;
+pub mod flac;
+pub mod sf2;
+pub mod sfz;
+pub mod sidecar;
+pub mod tags;
+pub mod wav;
+
+use std::borrow::Cow;
+use std::io::Write;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use data::config::sample_conversion::BitDepth;
+use data::config::sample_ripping::{Channels, ExportFormat};
+use data::config::{SampleConversionConfig, SampleProcessingConfig, SampleRippingConfig};
+
+use xmodits_lib::dsp;
+use xmodits_lib::interface::audio::AudioTrait;
+use xmodits_lib::interface::sample::{Channel, Depth, Loop, LoopType};
+use xmodits_lib::{Error, Sample};
+
+use tags::{ModuleInfo, Tags};
+
+use crate::dsp::dither::quantize;
+use crate::dsp::resample::{convert_position, resample};
+
+use chunk::Endian;
+
+/// Trackers play a sample at its C-5 rate when C-5 is played.
+///
+/// Exported samples keep that rate, or are resampled without changing their pitch,
+/// so whatever the rate, the root note is always C-5, i.e. MIDI note 60, without any fine tuning.
+pub const ROOT_NOTE: u8 = 60;
+
+pub struct Exporter {
+    format: ExportFormat,
+    /// xmodits' exporter, ``None`` if the format is handled entirely by us.
+    inner: Option<Box<dyn AudioTrait>>,
+    processing: SampleProcessingConfig,
+    conversion: SampleConversionConfig,
+    channels: Channels,
+    embed: Embed,
+}
+
+/// Extra information embedded in WAV, AIFF and FLAC files
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Embed {
+    pub loop_points: bool,
+    pub tags: bool,
+}
+
+impl Embed {
+    pub fn is_active(&self) -> bool {
+        self.loop_points || self.tags
+    }
+}
+
+impl Exporter {
+    pub fn new(cfg: &SampleRippingConfig) -> Self {
+        Self {
+            format: cfg.exported_format,
+            inner: cfg.exported_format.audio_format().map(|f| f.get_impl()),
+            processing: cfg.processing,
+            conversion: cfg.conversion,
+            channels: cfg.channels,
+            embed: Embed {
+                loop_points: cfg.embed_loop_points,
+                tags: cfg.embed_tags,
+            },
+        }
+    }
+
+    pub fn extension(&self) -> &str {
+        self.format.extension()
+    }
+
+    /// Write the sample, ``module`` describes where it came from.
+    ///
+    /// Only the given ``side`` of a stereo sample is written if it's set.
+    pub fn write(
+        &self,
+        smp: &Sample,
+        pcm: Cow<[u8]>,
+        module: &ModuleInfo,
+        side: Option<Side>,
+        writer: &mut dyn Write,
+    ) -> Result<Written, Error> {
+        let passthrough = side.is_none() && self.is_passthrough(smp);
+
+        if let Some(inner) = self.inner.as_ref().filter(|_| passthrough) {
+            inner.write(smp, pcm, writer)?;
+            return Ok(self.written(Written::from(smp)));
+        }
+
+        let tags = self.embed.tags.then(|| Tags::new(module, smp));
+        let audio = self.render(smp, pcm, side);
+        let written = self.written(Written::from(&audio));
+
+        match self.format {
+            ExportFormat::WAV => wav::write(writer, &audio, &self.embed, tags.as_ref())?,
+            ExportFormat::AIFF => aiff::write(writer, &audio, &self.embed, tags.as_ref())?,
+            ExportFormat::FLAC => flac::write(writer, &audio, &self.embed, tags.as_ref())?,
+            ExportFormat::RAW => writer.write_all(&audio.encode(Endian::Little, true))?,
+            ExportFormat::ITS | ExportFormat::S3I | ExportFormat::IFF => {
+                let inner = self
+                    .inner
+                    .as_ref()
+                    .expect("xmodits provides an exporter for tracker formats");
+
+                let (smp, pcm) = audio.into_sample(smp);
+                inner.write(&smp, Cow::Owned(pcm), writer)?
+            }
+        };
+
+        Ok(written)
+    }
+
+    pub fn format(&self) -> ExportFormat {
+        self.format
+    }
+
+    /// Stereo samples are written to separate files if they're split.
+    pub fn splits(&self, smp: &Sample) -> bool {
+        self.channels == Channels::Split && smp.is_stereo()
+    }
+
+    /// 8 bit WAV files are always unsigned
+    fn written(&self, mut written: Written) -> Written {
+        if self.format == ExportFormat::WAV && written.bits == 8 {
+            written.signed = false;
+        }
+        written
+    }
+
+    /// If nothing needs to be changed, the sample can be handed directly to xmodits' exporter.
+    fn is_passthrough(&self, smp: &Sample) -> bool {
+        let embeds =
+            self.embed.is_active() && matches!(self.format, ExportFormat::WAV | ExportFormat::AIFF);
+
+        let remixed = match self.channels {
+            Channels::Keep => false,
+            Channels::Mono | Channels::Split => smp.is_stereo(),
+            Channels::Stereo => !smp.is_stereo(),
+        };
+
+        // FLAC is always handled by us
+        self.inner.is_some()
+            && !(self.processing.is_active() || self.conversion.is_active() || embeds || remixed)
+    }
+
+    fn render(&self, smp: &Sample, pcm: Cow<[u8]>, side: Option<Side>) -> Audio {
+        self.render_channels(smp, pcm, self.bit_depth(smp), side)
+    }
+
+    /// Decode the sample, run it through the processing chain,
+    /// then convert it to the target sample rate and the given bit depth.
+    ///
+    /// The samples are floating point if ``bits`` is ``None``.
+    pub fn render_as(&self, smp: &Sample, pcm: Cow<[u8]>, bits: Option<u8>) -> Audio {
+        self.render_channels(smp, pcm, bits, None)
+    }
+
+    fn render_channels(
+        &self,
+        smp: &Sample,
+        pcm: Cow<[u8]>,
+        bits: Option<u8>,
+        side: Option<Side>,
+    ) -> Audio {
+        let mut buf = dsp::SampleBuffer::from(dsp::RawSample::new(smp, pcm)).buf;
+
+        match self.channels {
+            Channels::Mono => crate::dsp::downmix(&mut buf),
+            Channels::Stereo => crate::dsp::duplicate(&mut buf),
+            Channels::Keep | Channels::Split => (),
+        }
+
+        let kept = crate::dsp::process(&mut buf, smp.rate, &self.processing);
+        let mut looping = adjust_loop(&smp.looping, kept);
+        let mut rate = smp.rate;
+
+        if self.conversion.rate != 0 && self.conversion.rate != rate {
+            let target = self.conversion.rate;
+            resample(&mut buf, rate, target, self.conversion.resampler);
+
+            if looping.kind() != LoopType::Off {
+                looping = Loop::new(
+                    convert_position(looping.start(), rate, target),
+                    convert_position(looping.stop(), rate, target),
+                    looping.kind(),
+                );
+            }
+
+            rate = target;
+        }
+
+        // Split after processing so that both sides are treated the same way
+        if let Some(side) = side.filter(|_| buf.len() > 1) {
+            buf = vec![buf.swap_remove(side.index())];
+        }
+
+        let data = match bits {
+            None => Data::Float(interleave(&buf).collect()),
+            Some(bits) => {
+                let dither = self.conversion.dither && bits < smp.bits();
+                let mut rng = rand::thread_rng();
+
+                Data::Int {
+                    bits,
+                    samples: interleave(&buf)
+                        .map(|sample| quantize(sample, bits, dither, &mut rng))
+                        .collect(),
+                }
+            }
+        };
+
+        Audio {
+            channels: buf.len() as u16,
+            rate,
+            looping,
+            data,
+        }
+    }
+
+    /// The bit depth of the exported sample, ``None`` if it's floating point.
+    ///
+    /// FLAC can't store floating point samples, so they're written as 24 bit,
+    /// and tracker formats can only store 8 or 16 bit samples.
+    fn bit_depth(&self, smp: &Sample) -> Option<u8> {
+        let bits = match self.conversion.bit_depth {
+            BitDepth::Original => Some(smp.bits()),
+            BitDepth::Bits8 => Some(8),
+            BitDepth::Bits16 => Some(16),
+            BitDepth::Bits24 => Some(24),
+            BitDepth::Float32 => None,
+        };
+
+        match self.format {
+            ExportFormat::WAV | ExportFormat::AIFF | ExportFormat::RAW => bits,
+            ExportFormat::FLAC => bits.or(Some(24)),
+            _ => match bits {
+                Some(8) => Some(8),
+                _ => Some(16),
+            },
+        }
+    }
+}
+
+/// Describes a sample as it was written, which can differ from the original
+/// if it was processed or converted.
+#[derive(Debug, Clone, Copy)]
+pub struct Written {
+    pub rate: u32,
+    pub bits: u8,
+    pub signed: bool,
+    pub float: bool,
+    pub channels: u16,
+    pub frames: usize,
+    pub looping: Loop,
+}
+
+impl From<&Sample> for Written {
+    fn from(smp: &Sample) -> Self {
+        Self {
+            rate: smp.rate,
+            bits: smp.bits(),
+            signed: smp.is_signed(),
+            float: false,
+            channels: match smp.is_stereo() {
+                true => 2,
+                false => 1,
+            },
+            frames: smp.length_frames(),
+            looping: smp.looping,
+        }
+    }
+}
+
+impl From<&Audio> for Written {
+    fn from(audio: &Audio) -> Self {
+        Self {
+            rate: audio.rate,
+            bits: audio.data.bits(),
+            signed: true,
+            float: matches!(audio.data, Data::Float(_)),
+            channels: audio.channels,
+            frames: audio.frames(),
+            looping: audio.looping,
+        }
+    }
+}
+
+/// A sample that has been exported
+#[derive(Debug, Clone)]
+pub struct Exported {
+    pub path: PathBuf,
+    pub sample: Sample,
+    pub written: Written,
+    /// Set if only one channel of a stereo sample was exported
+    pub side: Option<Side>,
+}
+
+/// A channel of a stereo sample, used when stereo samples are split into separate files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    pub const BOTH: [Self; 2] = [Self::Left, Self::Right];
+
+    pub fn index(&self) -> usize {
+        match self {
+            Side::Left => 0,
+            Side::Right => 1,
+        }
+    }
+
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Side::Left => "_L",
+            Side::Right => "_R",
+        }
+    }
+
+    /// Insert the suffix before the file extension, e.g. "01 - kick.wav" -> "01 - kick_L.wav"
+    pub fn rename(&self, filename: &str) -> String {
+        match filename.rsplit_once('.') {
+            Some((stem, extension)) => format!("{}{}.{}", stem, self.suffix(), extension),
+            None => format!("{}{}", filename, self.suffix()),
+        }
+    }
+}
+
+/// A decoded sample that is ready to be written
+pub struct Audio {
+    pub channels: u16,
+    pub rate: u32,
+    pub looping: Loop,
+    pub data: Data,
+}
+
+/// Interleaved samples
+pub enum Data {
+    Int { bits: u8, samples: Vec<i32> },
+    Float(Vec<f32>),
+}
+
+impl Data {
+    pub fn bits(&self) -> u8 {
+        match self {
+            Data::Int { bits, .. } => *bits,
+            Data::Float(_) => 32,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Data::Int { samples, .. } => samples.len(),
+            Data::Float(samples) => samples.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Audio {
+    pub fn frames(&self) -> usize {
+        self.data.len() / (self.channels.max(1) as usize)
+    }
+
+    /// Serialize the samples.
+    ///
+    /// 8 bit samples are unsigned unless ``signed_8_bit`` is set.
+    pub fn encode(&self, endian: Endian, signed_8_bit: bool) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.data.len() * (self.data.bits() as usize / 8));
+
+        match &self.data {
+            Data::Int { bits: 8, samples } => {
+                out.extend(samples.iter().map(|s| match signed_8_bit {
+                    true => *s as i8 as u8,
+                    false => (*s + 128) as u8,
+                }))
+            }
+            Data::Int { bits: 16, samples } => samples
+                .iter()
+                .for_each(|s| out.extend_from_slice(&endian.u16(*s as i16 as u16))),
+            Data::Int { samples, .. } => samples.iter().for_each(|s| match endian {
+                Endian::Little => out.extend_from_slice(&s.to_le_bytes()[..3]),
+                Endian::Big => out.extend_from_slice(&s.to_be_bytes()[1..]),
+            }),
+            Data::Float(samples) => samples
+                .iter()
+                .for_each(|s| out.extend_from_slice(&endian.u32(s.to_bits()))),
+        };
+
+        out
+    }
+
+    /// Convert to a sample that xmodits' exporters can understand.
+    ///
+    /// Must be 8 or 16 bit.
+    fn into_sample(self, smp: &Sample) -> (Sample, Vec<u8>) {
+        let pcm = self.encode(Endian::Little, true);
+
+        let sample = Sample {
+            length: pcm.len() as u32,
+            rate: self.rate,
+            depth: match self.data.bits() {
+                8 => Depth::I8,
+                _ => Depth::I16,
+            },
+            channel: match self.channels {
+                1 => Channel::Mono,
+                _ => Channel::Stereo { interleaved: true },
+            },
+            looping: self.looping,
+            ..smp.clone()
+        };
+
+        (sample, pcm)
+    }
+}
+
+fn interleave(buf: &[Vec<f32>]) -> impl Iterator<Item = f32> + '_ {
+    (0..crate::dsp::frames(buf)).flat_map(move |frame| buf.iter().map(move |c| c[frame]))
+}
+
+/// Shift the loop points to match the frames that were kept.
+///
+/// The loop is disabled if it no longer fits in the sample.
+fn adjust_loop(looping: &Loop, kept: Range<usize>) -> Loop {
+    let offset = kept.start as u32;
+    let length = kept.len() as u32;
+
+    let start = looping.start().saturating_sub(offset);
+    let stop = looping.stop().saturating_sub(offset).min(length);
+
+    match looping.kind() {
+        LoopType::Off => *looping,
+        _ if start >= stop => Loop::new(0, 0, LoopType::Off),
+        kind => Loop::new(start, stop, kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Body of the first chunk with the given id
+    fn chunk<'a>(file: &'a [u8], id: &[u8; 4]) -> &'a [u8] {
+        let start = file
+            .windows(4)
+            .position(|window| window == id)
+            .expect("chunk should be written")
+            + 8;
+
+        &file[start..]
+    }
+
+    #[test]
+    fn root_note_ignores_rate() {
+        let audio = Audio {
+            channels: 1,
+            rate: 16726,
+            looping: Loop::new(0, 0, LoopType::Off),
+            data: Data::Int {
+                bits: 16,
+                samples: vec![0; 16],
+            },
+        };
+
+        let embed = Embed {
+            loop_points: true,
+            tags: false,
+        };
+
+        let mut file = Vec::new();
+        wav::write(&mut file, &audio, &embed, None).unwrap();
+
+        let smpl = chunk(&file, b"smpl");
+        let field = |index: usize| u32::from_le_bytes(smpl[index * 4..][..4].try_into().unwrap());
+        assert_eq!(field(3), ROOT_NOTE as u32, "unity note");
+        assert_eq!(field(4), 0, "pitch fraction");
+
+        let mut file = Vec::new();
+        aiff::write(&mut file, &audio, &embed, None).unwrap();
+
+        let inst = chunk(&file, b"INST");
+        assert_eq!(inst[0], ROOT_NOTE, "base note");
+        assert_eq!(inst[1], 0, "detune");
+    }
+}
@@ -0,0 +1,164 @@
+//! Audio Interchange File Format
+//!
+//! Floating point samples are written as AIFF-C.
+
+use std::io::{self, Write};
+
+use super::chunk::{write_container, Chunk, Endian};
+use super::tags::Tags;
+use super::{Audio, Data, Embed, ROOT_NOTE};
+
+use xmodits_lib::interface::sample::LoopType;
+
+const AIFC_VERSION_1: u32 = 0xA2805140;
+
+const LOOP_START_MARKER: u16 = 1;
+const LOOP_END_MARKER: u16 = 2;
+
+pub fn write(
+    writer: &mut dyn Write,
+    audio: &Audio,
+    embed: &Embed,
+    tags: Option<&Tags>,
+) -> io::Result<()> {
+    let is_float = matches!(audio.data, Data::Float(_));
+
+    let mut chunks = Vec::new();
+
+    if is_float {
+        chunks.push(Chunk::new(b"FVER", AIFC_VERSION_1.to_be_bytes().to_vec()));
+    }
+
+    chunks.push(comm(audio, is_float));
+
+    if embed.loop_points {
+        if audio.looping.kind() != LoopType::Off {
+            chunks.push(mark(audio));
+        }
+        chunks.push(inst(audio));
+    }
+
+    if let Some(tags) = tags {
+        if !tags.title().is_empty() {
+            chunks.push(Chunk::new(b"NAME", tags.title().as_bytes().to_vec()));
+        }
+        chunks.push(Chunk::new(b"ANNO", tags.annotation().into_bytes()));
+    }
+
+    chunks.push(ssnd(audio));
+
+    let form = match is_float {
+        true => b"AIFC",
+        false => b"AIFF",
+    };
+
+    write_container(writer, b"FORM", form, &chunks, Endian::Big)
+}
+
+fn comm(audio: &Audio, is_float: bool) -> Chunk {
+    let mut body = Vec::with_capacity(24);
+    body.extend_from_slice(&audio.channels.to_be_bytes());
+    body.extend_from_slice(&(audio.frames() as u32).to_be_bytes());
+    body.extend_from_slice(&(audio.data.bits() as u16).to_be_bytes());
+    body.extend_from_slice(&extended(audio.rate));
+
+    if is_float {
+        const NAME: &[u8] = b"32-bit floating point";
+
+        body.extend_from_slice(b"fl32");
+        push_pstring(&mut body, NAME);
+    }
+
+    Chunk::new(b"COMM", body)
+}
+
+/// Markers for the loop points
+fn mark(audio: &Audio) -> Chunk {
+    let markers: [(u16, u32, &[u8]); 2] = [
+        (LOOP_START_MARKER, audio.looping.start(), b"beg loop"),
+        (LOOP_END_MARKER, audio.looping.stop(), b"end loop"),
+    ];
+
+    let mut body = Vec::new();
+    body.extend_from_slice(&(markers.len() as u16).to_be_bytes());
+
+    for (id, position, name) in markers {
+        body.extend_from_slice(&id.to_be_bytes());
+        body.extend_from_slice(&position.to_be_bytes());
+        push_pstring(&mut body, name);
+    }
+
+    Chunk::new(b"MARK", body)
+}
+
+/// Instrument chunk, stores the root note and which markers to loop between.
+///
+/// AIFF can't loop backwards, so backward loops are written as forward loops.
+fn inst(audio: &Audio) -> Chunk {
+    let play_mode: u16 = match audio.looping.kind() {
+        LoopType::Off => 0,
+        LoopType::Forward | LoopType::Backward => 1,
+        LoopType::PingPong => 2,
+    };
+
+    let (begin, end) = match play_mode {
+        0 => (0, 0),
+        _ => (LOOP_START_MARKER, LOOP_END_MARKER),
+    };
+
+    let mut body = vec![
+        ROOT_NOTE, // base note
+        0,         // detune
+        0,         // low note
+        127,       // high note
+        1,         // low velocity
+        127,       // high velocity
+    ];
+
+    body.extend_from_slice(&0_u16.to_be_bytes()); // gain
+
+    // sustain loop
+    body.extend_from_slice(&play_mode.to_be_bytes());
+    body.extend_from_slice(&begin.to_be_bytes());
+    body.extend_from_slice(&end.to_be_bytes());
+
+    // release loop
+    body.extend_from_slice(&[0; 6]);
+
+    Chunk::new(b"INST", body)
+}
+
+/// Pascal strings are padded to an even length
+fn push_pstring(body: &mut Vec<u8>, string: &[u8]) {
+    body.push(string.len() as u8);
+    body.extend_from_slice(string);
+
+    if (string.len() + 1) & 1 == 1 {
+        body.push(0);
+    }
+}
+
+fn ssnd(audio: &Audio) -> Chunk {
+    let mut body = Vec::new();
+    body.extend_from_slice(&0_u32.to_be_bytes()); // offset
+    body.extend_from_slice(&0_u32.to_be_bytes()); // block size
+    body.extend_from_slice(&audio.encode(Endian::Big, true));
+
+    Chunk::new(b"SSND", body)
+}
+
+/// Convert the sample rate to an 80 bit IEEE 754 extended precision float
+fn extended(rate: u32) -> [u8; 10] {
+    let mut out = [0u8; 10];
+
+    if rate == 0 {
+        return out;
+    }
+
+    let exponent = 31 - rate.leading_zeros();
+    let mantissa = (rate as u64) << (63 - exponent);
+
+    out[..2].copy_from_slice(&(16383 + exponent as u16).to_be_bytes());
+    out[2..].copy_from_slice(&mantissa.to_be_bytes());
+    out
+}
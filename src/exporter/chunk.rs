@@ -0,0 +1,86 @@
+//! IFF style chunks, shared by the WAV and AIFF writers.
+
+use std::io::{self, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+impl Endian {
+    pub fn u16(self, value: u16) -> [u8; 2] {
+        match self {
+            Endian::Little => value.to_le_bytes(),
+            Endian::Big => value.to_be_bytes(),
+        }
+    }
+
+    pub fn u32(self, value: u32) -> [u8; 4] {
+        match self {
+            Endian::Little => value.to_le_bytes(),
+            Endian::Big => value.to_be_bytes(),
+        }
+    }
+}
+
+pub struct Chunk {
+    pub id: [u8; 4],
+    pub body: Vec<u8>,
+}
+
+impl Chunk {
+    pub fn new(id: &[u8; 4], body: Vec<u8>) -> Self {
+        Self { id: *id, body }
+    }
+
+    /// Size of the chunk including its header and padding
+    pub fn size(&self) -> u32 {
+        8 + self.body.len() as u32 + (self.body.len() as u32 & 1)
+    }
+
+    pub fn write(&self, writer: &mut dyn Write, endian: Endian) -> io::Result<()> {
+        writer.write_all(&self.id)?;
+        writer.write_all(&endian.u32(self.body.len() as u32))?;
+        writer.write_all(&self.body)?;
+
+        // Chunks are word aligned
+        if self.body.len() & 1 == 1 {
+            writer.write_all(&[0])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A ``LIST`` chunk containing ``chunks``
+pub fn list(form: &[u8; 4], chunks: &[Chunk], endian: Endian) -> Chunk {
+    let mut body = form.to_vec();
+
+    for chunk in chunks {
+        chunk.write(&mut body, endian).expect("Writing to a Vec");
+    }
+
+    Chunk::new(b"LIST", body)
+}
+
+/// Write a container chunk, i.e. ``RIFF`` or ``FORM``.
+pub fn write_container(
+    writer: &mut dyn Write,
+    id: &[u8; 4],
+    form: &[u8; 4],
+    chunks: &[Chunk],
+    endian: Endian,
+) -> io::Result<()> {
+    let size = 4 + chunks.iter().map(Chunk::size).sum::<u32>();
+
+    writer.write_all(id)?;
+    writer.write_all(&endian.u32(size))?;
+    writer.write_all(form)?;
+
+    for chunk in chunks {
+        chunk.write(writer, endian)?;
+    }
+
+    writer.flush()
+}
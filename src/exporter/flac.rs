@@ -0,0 +1,542 @@
+//! Free Lossless Audio Codec
+//!
+//! A small encoder that only uses FLAC's fixed predictors,
+//! which is more than enough for tracker samples.
+//!
+//! Loop points and tags are stored as Vorbis comments.
+//! The MD5 signature in STREAMINFO is left unset.
+
+use std::io::{self, Write};
+
+use xmodits_lib::interface::sample::LoopType;
+
+use super::tags::Tags;
+use super::{Audio, Data, Embed, ROOT_NOTE};
+
+const BLOCK_SIZE: usize = 4096;
+const MAX_PARTITION_ORDER: u32 = 8;
+const MAX_FIXED_ORDER: usize = 4;
+
+const STREAMINFO: u8 = 0;
+const VORBIS_COMMENT: u8 = 4;
+
+const INDEPENDENT: u8 = 1;
+const LEFT_SIDE: u8 = 8;
+const SIDE_RIGHT: u8 = 9;
+const MID_SIDE: u8 = 10;
+
+pub fn write(
+    writer: &mut dyn Write,
+    audio: &Audio,
+    embed: &Embed,
+    tags: Option<&Tags>,
+) -> io::Result<()> {
+    let Data::Int { bits, samples } = &audio.data else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "FLAC cannot store floating point samples",
+        ));
+    };
+
+    let bits = *bits as u32;
+    let channels = audio.channels.max(1) as usize;
+    let frames = audio.frames();
+    let block_size = BLOCK_SIZE.min(frames.max(16));
+
+    let comments = comments(audio, embed, tags);
+
+    writer.write_all(b"fLaC")?;
+    metadata_block(
+        writer,
+        STREAMINFO,
+        comments.is_none(),
+        &streaminfo(audio, bits, block_size, frames),
+    )?;
+
+    if let Some(comments) = comments {
+        metadata_block(writer, VORBIS_COMMENT, true, &comments)?;
+    }
+
+    for (number, block) in samples.chunks(block_size * channels).enumerate() {
+        writer.write_all(&frame(number as u32, block, channels, bits))?;
+    }
+
+    writer.flush()
+}
+
+fn metadata_block(writer: &mut dyn Write, kind: u8, last: bool, body: &[u8]) -> io::Result<()> {
+    let header = ((last as u32) << 31) | ((kind as u32) << 24) | body.len() as u32;
+
+    writer.write_all(&header.to_be_bytes())?;
+    writer.write_all(body)
+}
+
+fn streaminfo(audio: &Audio, bits: u32, block_size: usize, frames: usize) -> Vec<u8> {
+    let mut body = Vec::with_capacity(34);
+
+    // Minimum and maximum block size
+    body.extend_from_slice(&(block_size as u16).to_be_bytes());
+    body.extend_from_slice(&(block_size as u16).to_be_bytes());
+
+    // Minimum and maximum frame size, unknown
+    body.extend_from_slice(&[0; 6]);
+
+    let packed: u64 = ((audio.rate as u64 & 0xF_FFFF) << 44)
+        | (((audio.channels.max(1) - 1) as u64 & 0x7) << 41)
+        | (((bits - 1) as u64 & 0x1F) << 36)
+        | (frames as u64 & 0xF_FFFF_FFFF);
+
+    body.extend_from_slice(&packed.to_be_bytes());
+
+    // MD5 signature of the decoded audio, left as zero ("unknown"), which the format allows.
+    // Decoders still check every frame against its CRC.
+    body.extend_from_slice(&[0; 16]);
+    body
+}
+
+fn comments(audio: &Audio, embed: &Embed, tags: Option<&Tags>) -> Option<Vec<u8>> {
+    let mut fields = Vec::new();
+
+    if embed.loop_points {
+        let looping = audio.looping;
+
+        let kind = match looping.kind() {
+            LoopType::Off => None,
+            LoopType::Forward => Some("forward"),
+            LoopType::Backward => Some("backward"),
+            LoopType::PingPong => Some("pingpong"),
+        };
+
+        if let Some(kind) = kind {
+            fields.push(format!("LOOPSTART={}", looping.start()));
+            fields.push(format!("LOOPLENGTH={}", looping.stop() - looping.start()));
+            fields.push(format!("LOOPTYPE={}", kind));
+        }
+
+        fields.push(format!("ROOTNOTE={}", ROOT_NOTE));
+    }
+
+    if let Some(tags) = tags {
+        fields.push(format!("TITLE={}", tags.title()));
+        fields.push(format!("ALBUM={}", tags.module_title));
+        fields.push(format!("TRACKNUMBER={}", tags.index_raw));
+        fields.push(format!("COMMENT={}", tags.annotation()));
+    }
+
+    if fields.is_empty() {
+        return None;
+    }
+
+    let mut body = Vec::new();
+
+    push_string(&mut body, &Tags::software());
+    body.extend_from_slice(&(fields.len() as u32).to_le_bytes());
+    fields
+        .iter()
+        .for_each(|field| push_string(&mut body, field));
+
+    Some(body)
+}
+
+/// Vorbis comment strings are prefixed with their length
+fn push_string(body: &mut Vec<u8>, string: &str) {
+    body.extend_from_slice(&(string.len() as u32).to_le_bytes());
+    body.extend_from_slice(string.as_bytes());
+}
+
+fn frame(number: u32, block: &[i32], channels: usize, bits: u32) -> Vec<u8> {
+    let len = block.len() / channels;
+
+    let channel = |index: usize| -> Vec<i64> {
+        block
+            .iter()
+            .skip(index)
+            .step_by(channels)
+            .map(|sample| *sample as i64)
+            .collect()
+    };
+
+    let (assignment, subframes) = match channels {
+        2 => stereo(channel(0), channel(1), bits),
+        _ => (
+            (channels - 1) as u8,
+            (0..channels).map(|c| subframe(&channel(c), bits)).collect(),
+        ),
+    };
+
+    let mut out = BitWriter::default();
+    out.write(0b1111_1111_1111_1000, 16); // sync code, fixed block size
+    out.write(0b0111, 4); // block size is stored at the end of the header
+    out.write(0b0000, 4); // sample rate from STREAMINFO
+    out.write(assignment as u64, 4);
+    out.write(sample_size(bits), 3);
+    out.write(0, 1);
+
+    for byte in utf8(number) {
+        out.write(byte as u64, 8);
+    }
+
+    out.write((len - 1) as u64, 16);
+
+    let crc = crc8(&out.bytes);
+    out.write(crc as u64, 8);
+
+    for subframe in &subframes {
+        out.append(subframe);
+    }
+
+    out.align();
+
+    let crc = crc16(&out.bytes);
+    out.write(crc as u64, 16);
+
+    out.bytes
+}
+
+/// Some decoders don't read the sample size from STREAMINFO, so it's stored in every frame if it can be.
+fn sample_size(bits: u32) -> u64 {
+    match bits {
+        8 => 0b001,
+        12 => 0b010,
+        16 => 0b100,
+        20 => 0b101,
+        24 => 0b110,
+        _ => 0b000, // from STREAMINFO
+    }
+}
+
+/// Pick whichever channel decorrelation produces the smallest frame.
+fn stereo(left: Vec<i64>, right: Vec<i64>, bits: u32) -> (u8, Vec<BitWriter>) {
+    let side: Vec<i64> = left.iter().zip(&right).map(|(l, r)| l - r).collect();
+    let mid: Vec<i64> = left.iter().zip(&right).map(|(l, r)| (l + r) >> 1).collect();
+
+    let left = subframe(&left, bits);
+    let right = subframe(&right, bits);
+    let side = subframe(&side, bits + 1);
+    let mid = subframe(&mid, bits);
+
+    let assignment = [
+        (INDEPENDENT, left.len() + right.len()),
+        (LEFT_SIDE, left.len() + side.len()),
+        (SIDE_RIGHT, side.len() + right.len()),
+        (MID_SIDE, mid.len() + side.len()),
+    ]
+    .into_iter()
+    .min_by_key(|(_, len)| *len)
+    .map(|(assignment, _)| assignment)
+    .unwrap_or(INDEPENDENT);
+
+    let subframes = match assignment {
+        LEFT_SIDE => vec![left, side],
+        SIDE_RIGHT => vec![side, right],
+        MID_SIDE => vec![mid, side],
+        _ => vec![left, right],
+    };
+
+    (assignment, subframes)
+}
+
+fn subframe(samples: &[i64], bits: u32) -> BitWriter {
+    let mut out = BitWriter::default();
+
+    if samples.iter().all(|sample| *sample == samples[0]) {
+        subframe_header(&mut out, 0b000000);
+        out.write_signed(samples[0], bits);
+        return out;
+    }
+
+    let order = fixed_order(samples);
+
+    subframe_header(&mut out, 0b001000 | order as u64);
+    samples[..order]
+        .iter()
+        .for_each(|sample| out.write_signed(*sample, bits));
+    write_residual(&mut out, &residual(samples, order), samples.len(), order);
+
+    // Fall back to storing the samples as is if prediction didn't help
+    if out.len() >= 8 + samples.len() * bits as usize {
+        out = BitWriter::default();
+        subframe_header(&mut out, 0b000001);
+        samples
+            .iter()
+            .for_each(|sample| out.write_signed(*sample, bits));
+    }
+
+    out
+}
+
+fn subframe_header(out: &mut BitWriter, kind: u64) {
+    out.write(0, 1); // padding
+    out.write(kind, 6);
+    out.write(0, 1); // no wasted bits
+}
+
+/// Choose the fixed predictor with the smallest total residual.
+fn fixed_order(samples: &[i64]) -> usize {
+    (0..=MAX_FIXED_ORDER.min(samples.len()))
+        .min_by_key(|order| {
+            residual(samples, *order)
+                .iter()
+                .map(|r| r.unsigned_abs())
+                .sum::<u64>()
+        })
+        .unwrap_or_default()
+}
+
+fn residual(samples: &[i64], order: usize) -> Vec<i64> {
+    let s = samples;
+
+    (order..s.len())
+        .map(|n| match order {
+            0 => s[n],
+            1 => s[n] - s[n - 1],
+            2 => s[n] - 2 * s[n - 1] + s[n - 2],
+            3 => s[n] - 3 * s[n - 1] + 3 * s[n - 2] - s[n - 3],
+            _ => s[n] - 4 * s[n - 1] + 6 * s[n - 2] - 4 * s[n - 3] + s[n - 4],
+        })
+        .collect()
+}
+
+/// Rice code the residual, using whichever partition order is the smallest.
+fn write_residual(out: &mut BitWriter, residual: &[i64], block_size: usize, order: usize) {
+    let zigzag: Vec<u64> = residual
+        .iter()
+        .map(|r| ((r << 1) ^ (r >> 63)) as u64)
+        .collect();
+
+    let (partition_order, params, _) = (0..=MAX_PARTITION_ORDER)
+        .filter(|p| block_size % (1 << p) == 0 && (block_size >> p) > order)
+        .map(|p| {
+            let params: Vec<(u32, u64)> = partitions(&zigzag, block_size, order, p)
+                .map(rice_parameter)
+                .collect();
+            let bits = params.iter().map(|(_, bits)| bits + 5).sum::<u64>();
+
+            (p, params, bits)
+        })
+        .min_by_key(|(_, _, bits)| *bits)
+        .unwrap_or_else(|| (0, vec![rice_parameter(&zigzag)], 0));
+
+    // Parameters above 14 need the 5 bit variant
+    let param_bits = match params.iter().any(|(k, _)| *k > 14) {
+        true => 5,
+        false => 4,
+    };
+
+    out.write((param_bits - 4) as u64, 2);
+    out.write(partition_order as u64, 4);
+
+    for (partition, (k, _)) in partitions(&zigzag, block_size, order, partition_order).zip(params) {
+        out.write(k as u64, param_bits);
+
+        for value in partition {
+            out.write_unary(value >> k);
+            out.write(*value, k);
+        }
+    }
+}
+
+/// The first partition is shorter since it doesn't include the warm up samples.
+fn partitions<'a>(
+    zigzag: &'a [u64],
+    block_size: usize,
+    order: usize,
+    partition_order: u32,
+) -> impl Iterator<Item = &'a [u64]> {
+    let len = block_size >> partition_order;
+    let first = (len - order).min(zigzag.len());
+
+    std::iter::once(&zigzag[..first]).chain(zigzag[first..].chunks(len.max(1)))
+}
+
+/// Find the rice parameter with the fewest bits, returns the parameter and the number of bits.
+fn rice_parameter(partition: &[u64]) -> (u32, u64) {
+    let cost = |k: u32| -> u64 {
+        partition.len() as u64 * (k as u64 + 1) + partition.iter().map(|v| v >> k).sum::<u64>()
+    };
+
+    let mean = partition.iter().sum::<u64>() / (partition.len().max(1) as u64);
+    let estimate = match mean {
+        0 => 0,
+        mean => 63 - mean.leading_zeros(),
+    };
+
+    (estimate.saturating_sub(1)..=(estimate + 1).min(30))
+        .map(|k| (k, cost(k)))
+        .min_by_key(|(_, bits)| *bits)
+        .unwrap_or((0, cost(0)))
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u64,
+    bits: u32,
+}
+
+impl BitWriter {
+    /// Write the lowest ``n`` bits of ``value``, ``n`` must not exceed 32.
+    fn write(&mut self, value: u64, n: u32) {
+        let mask = (1_u64 << n) - 1;
+
+        self.acc = (self.acc << n) | (value & mask);
+        self.bits += n;
+
+        while self.bits >= 8 {
+            self.bits -= 8;
+            self.bytes.push((self.acc >> self.bits) as u8);
+        }
+
+        self.acc &= (1 << self.bits) - 1;
+    }
+
+    fn write_signed(&mut self, value: i64, n: u32) {
+        self.write(value as u64, n)
+    }
+
+    /// ``value`` zeros followed by a one
+    fn write_unary(&mut self, mut value: u64) {
+        while value >= 32 {
+            self.write(0, 32);
+            value -= 32;
+        }
+
+        self.write(1, value as u32 + 1);
+    }
+
+    fn append(&mut self, other: &BitWriter) {
+        other
+            .bytes
+            .iter()
+            .for_each(|byte| self.write(*byte as u64, 8));
+        self.write(other.acc, other.bits);
+    }
+
+    fn align(&mut self) {
+        if self.bits > 0 {
+            self.write(0, 8 - self.bits);
+        }
+    }
+
+    /// Length in bits
+    fn len(&self) -> usize {
+        self.bytes.len() * 8 + self.bits as usize
+    }
+}
+
+/// Frame numbers are encoded like UTF-8 characters, but can be up to 36 bits long.
+fn utf8(value: u32) -> Vec<u8> {
+    if value < 0x80 {
+        return vec![value as u8];
+    }
+
+    let len = match value {
+        v if v < 0x800 => 2,
+        v if v < 0x10000 => 3,
+        v if v < 0x200000 => 4,
+        v if v < 0x4000000 => 5,
+        _ => 6,
+    };
+
+    let mut out = vec![0; len];
+    let mut value = value;
+
+    for byte in out[1..].iter_mut().rev() {
+        *byte = 0x80 | (value & 0x3F) as u8;
+        value >>= 6;
+    }
+
+    out[0] = (0xFF00_u16 >> len) as u8 | value as u8;
+    out
+}
+
+fn crc8(data: &[u8]) -> u8 {
+    data.iter().fold(0, |crc, byte| {
+        (0..8).fold(crc ^ byte, |crc, _| match crc & 0x80 {
+            0 => crc << 1,
+            _ => (crc << 1) ^ 0x07,
+        })
+    })
+}
+
+fn crc16(data: &[u8]) -> u16 {
+    data.iter().fold(0, |crc, byte| {
+        (0..8).fold(crc ^ ((*byte as u16) << 8), |crc, _| match crc & 0x8000 {
+            0 => crc << 1,
+            _ => (crc << 1) ^ 0x8005,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use xmodits_lib::interface::sample::Loop;
+
+    use super::*;
+
+    /// Silence, a constant, a sine wave and full scale noise, to go through every kind of subframe.
+    fn signal(frames: usize, channels: usize, bits: u8) -> Vec<i32> {
+        let max = (1_i64 << (bits - 1)) - 1;
+        let mut seed = 0x1234_5678_u32;
+
+        (0..frames * channels)
+            .map(|i| {
+                let (frame, channel) = (i / channels, i % channels);
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                let noise = (seed >> 16) as f64 / 65536.0 - 0.5;
+
+                let value = match frame {
+                    0..=299 => 0.0,
+                    300..=599 => 0.25,
+                    600..=4999 => (frame as f64 * 0.05 * (channel + 1) as f64).sin() * 0.8,
+                    _ => noise * 2.0,
+                };
+
+                (value * max as f64)
+                    .round()
+                    .clamp(-(max as f64) - 1.0, max as f64) as i32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trip() {
+        for channels in [1, 2] {
+            for bits in [8, 16, 24] {
+                for frames in [1, 17, BLOCK_SIZE, 9001] {
+                    let samples = signal(frames, channels, bits);
+                    let audio = Audio {
+                        channels: channels as u16,
+                        rate: 22050,
+                        looping: Loop::new(0, 0, LoopType::Off),
+                        data: Data::Int {
+                            bits,
+                            samples: samples.clone(),
+                        },
+                    };
+
+                    let embed = Embed {
+                        loop_points: true,
+                        tags: false,
+                    };
+
+                    let mut file = Vec::new();
+                    write(&mut file, &audio, &embed, None).unwrap();
+
+                    let mut reader = claxon::FlacReader::new(file.as_slice()).unwrap();
+                    let info = reader.streaminfo();
+                    assert_eq!(info.channels as usize, channels);
+                    assert_eq!(info.bits_per_sample, bits as u32);
+                    assert_eq!(info.sample_rate, 22050);
+                    assert_eq!(info.samples, Some(frames as u64));
+
+                    let decoded: Vec<i32> = reader.samples().map(Result::unwrap).collect();
+                    assert!(
+                        decoded == samples,
+                        "{channels} channel(s), {bits} bit, {frames} frames"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,413 @@
+//! SoundFont 2
+//!
+//! xmodits doesn't read a module's instruments, so they can't be presets.
+//! Instead, there's a drum kit preset laid out like the SFZ instruments:
+//! each sample gets its own key, and plays at its original pitch on that key.
+//!
+//! Optionally, every sample also becomes its own instrument,
+//! with a preset that plays it across the whole keyboard around C-5.
+//!
+//! Samples are stored as 16 bit. Stereo samples are split into a linked left and right sample.
+
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+use tracing::warn;
+use xmodits_lib::interface::sample::LoopType;
+
+use super::chunk::{list, write_container, Chunk, Endian};
+use super::tags::{ModuleInfo, Tags};
+use super::{Audio, Data, ROOT_NOTE};
+
+/// Samples must be followed by at least 46 zero valued samples
+const SAMPLE_PADDING: usize = 46;
+
+/// General MIDI percussion bank
+const DRUM_KIT_BANK: u16 = 128;
+
+/// Number of presets in a bank
+const BANK_SIZE: usize = 128;
+
+/// First key used by the layout, C1 in most samplers.
+const FIRST_KEY: usize = 36;
+const LAST_KEY: usize = 127;
+
+const MONO_SAMPLE: u16 = 1;
+const RIGHT_SAMPLE: u16 = 2;
+const LEFT_SAMPLE: u16 = 4;
+
+// Generators
+const PAN: u16 = 17;
+const INSTRUMENT: u16 = 41;
+const KEY_RANGE: u16 = 43;
+const SAMPLE_ID: u16 = 53;
+const SAMPLE_MODES: u16 = 54;
+const OVERRIDING_ROOT_KEY: u16 = 58;
+
+/// A sample that will be stored in the SoundFont, must be 16 bit.
+pub struct SoundFontSample {
+    pub name: String,
+    pub audio: Audio,
+}
+
+pub fn write(
+    writer: &mut dyn Write,
+    module: &ModuleInfo,
+    samples: &[SoundFontSample],
+    sample_presets: bool,
+) -> io::Result<()> {
+    let name = match module.title.trim() {
+        "" => crate::utils::filename(&module.path).to_owned(),
+        title => title.to_owned(),
+    };
+
+    let mut builder = Builder::default();
+    let mut drum_kit = Vec::new();
+
+    // Start from the lowest key if there are too many samples to fit
+    let first_key = match samples.len() > (LAST_KEY - FIRST_KEY + 1) {
+        true => 0,
+        false => FIRST_KEY,
+    };
+
+    let mut keys = (first_key..=LAST_KEY).map(|key| key as u8).peekable();
+
+    for sample in samples {
+        if !sample_presets && keys.peek().is_none() {
+            warn!(
+                "No free keys left in the drum kit for \"{}\", it won't be stored",
+                sample.name
+            );
+            continue;
+        }
+
+        let zones = builder.add(sample);
+
+        if zones.is_empty() {
+            continue;
+        }
+
+        match keys.next() {
+            Some(key) => drum_kit.extend(zones.iter().map(|zone| zone.on_key(key))),
+            None => warn!(
+                "No free keys left in the drum kit for \"{}\", it only has its own preset",
+                sample.name
+            ),
+        }
+
+        if sample_presets {
+            builder.instruments.push(Instrument {
+                name: sample.name.clone(),
+                zones,
+            });
+        }
+    }
+
+    // With sample presets, each sample is its own melodic preset. The drum kit is the last preset.
+    let mut presets: Vec<Preset> = builder
+        .instruments
+        .iter()
+        .enumerate()
+        .map(|(index, instrument)| Preset {
+            name: instrument.name.clone(),
+            number: (index % BANK_SIZE) as u16,
+            bank: (index / BANK_SIZE) as u16,
+            instrument: index as u16,
+        })
+        .collect();
+
+    if !drum_kit.is_empty() {
+        presets.push(Preset {
+            name: name.clone(),
+            number: 0,
+            bank: DRUM_KIT_BANK,
+            instrument: builder.instruments.len() as u16,
+        });
+
+        builder.instruments.push(Instrument {
+            name: name.clone(),
+            zones: drum_kit,
+        });
+    }
+
+    let chunks = [
+        info(&name),
+        list(b"sdta", &[Chunk::new(b"smpl", builder.pcm)], Endian::Little),
+        pdta(&builder.headers, &builder.instruments, &presets),
+    ];
+
+    write_container(writer, b"RIFF", b"sfbk", &chunks, Endian::Little)
+}
+
+fn info(name: &str) -> Chunk {
+    let mut version = Vec::with_capacity(4);
+    version.extend_from_slice(&2_u16.to_le_bytes());
+    version.extend_from_slice(&1_u16.to_le_bytes());
+
+    let chunks = [
+        Chunk::new(b"ifil", version),
+        Chunk::new(b"isng", zstr("EMU8000")),
+        Chunk::new(b"INAM", zstr(name)),
+        Chunk::new(b"ISFT", zstr(&Tags::software())),
+    ];
+
+    list(b"INFO", &chunks, Endian::Little)
+}
+
+struct Preset {
+    name: String,
+    number: u16,
+    bank: u16,
+    instrument: u16,
+}
+
+struct Instrument {
+    name: String,
+    zones: Vec<Zone>,
+}
+
+/// An instrument zone, which plays a sample over a range of keys.
+#[derive(Clone)]
+struct Zone {
+    keys: RangeInclusive<u8>,
+    sample_id: u16,
+    looped: bool,
+    pan: Option<i16>,
+    /// Play the sample at its rate on this key, instead of around C-5.
+    fixed_key: Option<u8>,
+}
+
+impl Zone {
+    /// The same zone, but only playing on the given key
+    fn on_key(&self, key: u8) -> Self {
+        Self {
+            keys: key..=key,
+            fixed_key: Some(key),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Default)]
+struct Builder {
+    pcm: Vec<u8>,
+    headers: Vec<SampleHeader>,
+    instruments: Vec<Instrument>,
+}
+
+impl Builder {
+    /// Store the sample, returns the zones that play it across the whole keyboard.
+    fn add(&mut self, sample: &SoundFontSample) -> Vec<Zone> {
+        let audio = &sample.audio;
+
+        let Data::Int { bits: 16, samples } = &audio.data else {
+            warn!("\"{}\" isn't 16 bit, it won't be stored", sample.name);
+            return Vec::new();
+        };
+
+        let channels = audio.channels.max(1) as usize;
+        let looped = audio.looping.kind() != LoopType::Off;
+        let first_id = self.headers.len() as u16;
+
+        let kinds: &[(u16, &str, Option<i16>)] = match channels {
+            1 => &[(MONO_SAMPLE, "", None)],
+            _ => &[
+                (LEFT_SAMPLE, "L", Some(-500)),
+                (RIGHT_SAMPLE, "R", Some(500)),
+            ],
+        };
+
+        let mut zones = Vec::with_capacity(kinds.len());
+
+        for (channel, (kind, suffix, pan)) in kinds.iter().enumerate() {
+            let start = (self.pcm.len() / 2) as u32;
+
+            samples
+                .iter()
+                .skip(channel)
+                .step_by(channels)
+                .for_each(|s| self.pcm.extend_from_slice(&(*s as i16).to_le_bytes()));
+
+            let end = (self.pcm.len() / 2) as u32;
+            self.pcm.extend_from_slice(&[0; SAMPLE_PADDING * 2]);
+
+            let (start_loop, end_loop) = match looped {
+                true => (start + audio.looping.start(), start + audio.looping.stop()),
+                false => (start, end),
+            };
+
+            // Stereo samples are linked to each other
+            let link = match kinds.len() {
+                1 => 0,
+                _ => first_id + (1 - channel as u16),
+            };
+
+            self.headers.push(SampleHeader {
+                name: format!("{}{}", sample.name, suffix),
+                start,
+                end,
+                start_loop,
+                end_loop: end_loop.min(end),
+                rate: audio.rate,
+                link,
+                kind: *kind,
+            });
+
+            zones.push(Zone {
+                keys: 0..=127,
+                sample_id: first_id + channel as u16,
+                looped,
+                pan: *pan,
+                fixed_key: None,
+            });
+        }
+
+        zones
+    }
+}
+
+struct SampleHeader {
+    name: String,
+    start: u32,
+    end: u32,
+    start_loop: u32,
+    end_loop: u32,
+    rate: u32,
+    link: u16,
+    kind: u16,
+}
+
+/// The preset, instrument and sample headers
+fn pdta(headers: &[SampleHeader], instruments: &[Instrument], presets: &[Preset]) -> Chunk {
+    // Every preset has a single zone that uses a single instrument
+    let mut phdr = Vec::with_capacity((presets.len() + 1) * 38);
+
+    for (bag, preset) in presets.iter().enumerate() {
+        phdr.extend(name20(&preset.name));
+        phdr.extend_from_slice(&preset.number.to_le_bytes());
+        phdr.extend_from_slice(&preset.bank.to_le_bytes());
+        phdr.extend_from_slice(&(bag as u16).to_le_bytes());
+        phdr.extend_from_slice(&[0; 12]); // library, genre, morphology
+    }
+
+    phdr.extend(name20("EOP"));
+    phdr.extend_from_slice(&[0; 4]);
+    phdr.extend_from_slice(&(presets.len() as u16).to_le_bytes());
+    phdr.extend_from_slice(&[0; 12]);
+
+    let pbag: Vec<[u16; 2]> = (0..=presets.len()).map(|gen| [gen as u16, 0]).collect();
+    let mut pgen: Vec<[u16; 2]> = presets
+        .iter()
+        .map(|preset| [INSTRUMENT, preset.instrument])
+        .collect();
+
+    pgen.push([0, 0]);
+
+    let mut inst = Vec::with_capacity((instruments.len() + 1) * 22);
+    let mut igen: Vec<[u16; 2]> = Vec::new();
+    let mut ibag: Vec<[u16; 2]> = Vec::new();
+
+    for instrument in instruments {
+        inst.extend(name20(&instrument.name));
+        inst.extend_from_slice(&(ibag.len() as u16).to_le_bytes());
+
+        for zone in &instrument.zones {
+            ibag.push([igen.len() as u16, 0]);
+
+            // The key range must come first, and the sample ID last.
+            igen.push([
+                KEY_RANGE,
+                u16::from_le_bytes([*zone.keys.start(), *zone.keys.end()]),
+            ]);
+
+            if let Some(pan) = zone.pan {
+                igen.push([PAN, pan as u16]);
+            }
+
+            // The sample plays at its rate on its key, just like the tracker would play it at C-5.
+            if let Some(key) = zone.fixed_key {
+                igen.push([OVERRIDING_ROOT_KEY, key as u16]);
+            }
+
+            igen.push([SAMPLE_MODES, zone.looped as u16]);
+            igen.push([SAMPLE_ID, zone.sample_id]);
+        }
+    }
+
+    inst.extend(name20("EOI"));
+    inst.extend_from_slice(&(ibag.len() as u16).to_le_bytes());
+
+    ibag.push([igen.len() as u16, 0]);
+    igen.push([0, 0]);
+
+    let mut shdr = Vec::with_capacity((headers.len() + 1) * 46);
+
+    for header in headers {
+        shdr.extend(name20(&header.name));
+        [
+            header.start,
+            header.end,
+            header.start_loop,
+            header.end_loop,
+            header.rate,
+        ]
+        .iter()
+        .for_each(|field| shdr.extend_from_slice(&field.to_le_bytes()));
+        shdr.push(ROOT_NOTE); // original pitch
+        shdr.push(0); // pitch correction
+        shdr.extend_from_slice(&header.link.to_le_bytes());
+        shdr.extend_from_slice(&header.kind.to_le_bytes());
+    }
+
+    shdr.extend(name20("EOS"));
+    shdr.extend_from_slice(&[0; 26]);
+
+    let chunks = [
+        Chunk::new(b"phdr", phdr),
+        Chunk::new(b"pbag", records(&pbag)),
+        Chunk::new(b"pmod", vec![0; 10]),
+        Chunk::new(b"pgen", records(&pgen)),
+        Chunk::new(b"inst", inst),
+        Chunk::new(b"ibag", records(&ibag)),
+        Chunk::new(b"imod", vec![0; 10]),
+        Chunk::new(b"igen", records(&igen)),
+        Chunk::new(b"shdr", shdr),
+    ];
+
+    list(b"pdta", &chunks, Endian::Little)
+}
+
+fn records(records: &[[u16; 2]]) -> Vec<u8> {
+    records
+        .iter()
+        .flat_map(|[a, b]| [a.to_le_bytes(), b.to_le_bytes()])
+        .flatten()
+        .collect()
+}
+
+/// Names are stored in 20 bytes, and must be null terminated.
+fn name20(name: &str) -> Vec<u8> {
+    let mut out: Vec<u8> = name
+        .chars()
+        .map(|c| match c.is_ascii() && !c.is_ascii_control() {
+            true => c as u8,
+            false => b'_',
+        })
+        .take(19)
+        .collect();
+
+    out.resize(20, 0);
+    out
+}
+
+/// Null terminated string, padded to an even length
+fn zstr(string: &str) -> Vec<u8> {
+    let mut out = string.as_bytes().to_vec();
+    out.push(0);
+
+    if out.len() & 1 == 1 {
+        out.push(0);
+    }
+
+    out
+}
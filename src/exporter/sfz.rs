@@ -0,0 +1,114 @@
+//! SFZ instruments
+//!
+//! Samples are laid out like a drum kit: each sample gets its own key,
+//! and plays at its original pitch on that key.
+//! Their root note is still written, so they can be played melodically by removing ``pitch_keytrack``.
+//!
+//! The sides of a split stereo sample share a key, and are panned hard left and right.
+//!
+//! NOTE: xmodits doesn't read a module's instruments, or the volume and panning of its samples,
+//! so regions can't be mapped the way the module's instruments do, and don't set a volume or panning.
+
+use std::io::{self, Write};
+use std::path::Path;
+
+use xmodits_lib::interface::sample::LoopType;
+
+use super::tags::{ModuleInfo, Tags};
+use super::{Exported, Side, ROOT_NOTE};
+
+/// First key used by the layout, C1 in most samplers.
+const FIRST_KEY: usize = 36;
+const LAST_KEY: usize = 127;
+
+pub fn write(writer: &mut dyn Write, module: &ModuleInfo, samples: &[Exported]) -> io::Result<()> {
+    writeln!(writer, "// Generated by {}", Tags::software())?;
+    writeln!(writer, "// Module: {} ({})", module.title, module.format)?;
+    writeln!(writer, "// Source: {}", module.path.display())?;
+
+    // Both sides of a split stereo sample share the same key
+    let needs_key = |exported: &&Exported| exported.side != Some(Side::Right);
+
+    // Start from the lowest key if there are too many samples to fit
+    let first_key = match samples.iter().filter(needs_key).count() > (LAST_KEY - FIRST_KEY + 1) {
+        true => 0,
+        false => FIRST_KEY,
+    };
+
+    let mut keys = first_key..=LAST_KEY;
+    let mut key = None;
+
+    for exported in samples {
+        if needs_key(&exported) {
+            key = keys.next();
+        }
+
+        match key {
+            Some(key) => {
+                writeln!(writer)?;
+                region(writer, key, exported)?;
+            }
+            None => writeln!(
+                writer,
+                "\n// No free keys left for {}",
+                relative(&exported.path)
+            )?,
+        }
+    }
+
+    writer.flush()
+}
+
+fn region(writer: &mut dyn Write, key: usize, exported: &Exported) -> io::Result<()> {
+    let smp = &exported.sample;
+    let written = &exported.written;
+
+    match smp.name_pretty().trim() {
+        "" => writeln!(writer, "// #{}", smp.index_raw())?,
+        name => writeln!(writer, "// #{} - {}", smp.index_raw(), name)?,
+    };
+
+    writeln!(writer, "<region>")?;
+    writeln!(writer, "sample={}", relative(&exported.path))?;
+
+    // The sample plays at its rate on its key, just like the tracker would play it at C-5.
+    writeln!(
+        writer,
+        "lokey={key} hikey={key} pitch_keycenter={ROOT_NOTE} pitch_keytrack=0"
+    )?;
+
+    match exported.side {
+        Some(Side::Left) => writeln!(writer, "pan=-100")?,
+        Some(Side::Right) => writeln!(writer, "pan=100")?,
+        None => (),
+    };
+
+    let looping = written.looping;
+
+    let loop_type = match looping.kind() {
+        LoopType::Off => None,
+        LoopType::Forward => Some("forward"),
+        LoopType::Backward => Some("backward"),
+        LoopType::PingPong => Some("alternate"),
+    };
+
+    match loop_type {
+        None => writeln!(writer, "loop_mode=no_loop")?,
+        Some(loop_type) => {
+            writeln!(writer, "loop_mode=loop_continuous loop_type={loop_type}")?;
+            writeln!(
+                writer,
+                "loop_start={} loop_end={}",
+                looping.start(),
+                looping.stop().saturating_sub(1) // inclusive
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Samples are placed next to the SFZ file.
+fn relative(path: &Path) -> String {
+    crate::utils::filename(path).to_owned()
+}
@@ -0,0 +1,125 @@
+//! JSON files that describe the exported samples
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+use xmodits_lib::interface::sample::LoopType;
+
+use super::tags::ModuleInfo;
+use super::{Exported, Side};
+
+use data::config::sample_ripping::ExportFormat;
+
+#[derive(Serialize)]
+pub struct SampleMetadata {
+    pub file: String,
+    pub name: String,
+    pub filename: Option<String>,
+    pub index_raw: usize,
+    pub rate: u32,
+    pub bits: u8,
+    pub signed: bool,
+    pub float: bool,
+    pub channels: u16,
+    /// Which side of a split stereo sample was exported
+    pub side: Option<&'static str>,
+    pub frames: usize,
+    #[serde(rename = "loop")]
+    pub looping: LoopMetadata,
+    pub source: SourceMetadata,
+    pub exporter: String,
+}
+
+#[derive(Serialize)]
+pub struct LoopMetadata {
+    pub kind: &'static str,
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(Serialize)]
+pub struct SourceMetadata {
+    pub path: String,
+    pub format: String,
+    pub title: String,
+}
+
+#[derive(Serialize)]
+struct ModuleMetadata<'a> {
+    source: SourceMetadata,
+    samples: &'a [SampleMetadata],
+}
+
+impl SampleMetadata {
+    pub fn new(module: &ModuleInfo, exported: &Exported, format: ExportFormat) -> Self {
+        let smp = &exported.sample;
+        let written = &exported.written;
+
+        let filename = Some(smp.filename_pretty().trim().to_owned()).filter(|f| !f.is_empty());
+
+        Self {
+            file: crate::utils::filename(&exported.path).to_owned(),
+            name: smp.name_pretty().trim().to_owned(),
+            filename,
+            index_raw: smp.index_raw() as usize,
+            rate: written.rate,
+            bits: written.bits,
+            signed: written.signed,
+            float: written.float,
+            channels: written.channels,
+            side: exported.side.map(|side| match side {
+                Side::Left => "left",
+                Side::Right => "right",
+            }),
+            frames: written.frames,
+            looping: LoopMetadata {
+                kind: match written.looping.kind() {
+                    LoopType::Off => "off",
+                    LoopType::Forward => "forward",
+                    LoopType::Backward => "backward",
+                    LoopType::PingPong => "pingpong",
+                },
+                start: written.looping.start(),
+                end: written.looping.stop(),
+            },
+            source: SourceMetadata::new(module),
+            exporter: format.to_string(),
+        }
+    }
+}
+
+impl SourceMetadata {
+    fn new(module: &ModuleInfo) -> Self {
+        Self {
+            path: module.path.display().to_string(),
+            format: module.format.clone(),
+            title: module.title.clone(),
+        }
+    }
+}
+
+/// Write ``<sample>.json`` next to the exported sample
+pub fn write_sample(metadata: &SampleMetadata, sample_path: &Path) -> io::Result<()> {
+    write_json(&sample_path.with_extension("json"), metadata)
+}
+
+pub fn write_module(
+    module: &ModuleInfo,
+    samples: &[SampleMetadata],
+    path: &Path,
+) -> io::Result<()> {
+    let metadata = ModuleMetadata {
+        source: SourceMetadata::new(module),
+        samples,
+    };
+
+    write_json(path, &metadata)
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut file, value)?;
+    file.flush()
+}
@@ -0,0 +1,69 @@
+//! Provenance of an exported sample
+
+use std::path::PathBuf;
+
+use xmodits_lib::Sample;
+
+/// Information about the module a sample was ripped from
+#[derive(Debug, Clone, Default)]
+pub struct ModuleInfo {
+    pub title: String,
+    pub format: String,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct Tags {
+    pub sample_name: String,
+    pub filename: Option<String>,
+    pub module_title: String,
+    pub module_format: String,
+    pub index_raw: usize,
+    pub source: PathBuf,
+}
+
+impl Tags {
+    pub fn new(module: &ModuleInfo, smp: &Sample) -> Self {
+        let filename = Some(smp.filename_pretty().trim().to_owned()).filter(|f| !f.is_empty());
+
+        Self {
+            sample_name: smp.name_pretty().trim().to_owned(),
+            filename,
+            module_title: module.title.clone(),
+            module_format: module.format.clone(),
+            index_raw: smp.index_raw() as usize,
+            source: module.path.clone(),
+        }
+    }
+
+    /// Name used to title the sample.
+    ///
+    /// Falls back to the original filename if the sample is unnamed.
+    pub fn title(&self) -> &str {
+        match (self.sample_name.as_str(), &self.filename) {
+            ("", Some(filename)) => filename,
+            (name, _) => name,
+        }
+    }
+
+    /// Summary of the sample's provenance
+    pub fn annotation(&self) -> String {
+        let mut lines = vec![
+            format!("Sample: {}", self.sample_name),
+            format!("Module: {}", self.module_title),
+            format!("Format: {}", self.module_format),
+            format!("Index: {}", self.index_raw),
+            format!("Source: {}", self.source.display()),
+        ];
+
+        if let Some(filename) = &self.filename {
+            lines.insert(1, format!("Original Filename: {}", filename));
+        }
+
+        lines.join("\n")
+    }
+
+    pub fn software() -> String {
+        format!("XMODITS v{}", env!("CARGO_PKG_VERSION"))
+    }
+}
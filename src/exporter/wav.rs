@@ -0,0 +1,137 @@
+//! Microsoft Wave
+
+use std::io::{self, Write};
+
+use super::chunk::{list, write_container, Chunk, Endian};
+use super::tags::Tags;
+use super::{Audio, Data, Embed, ROOT_NOTE};
+
+use xmodits_lib::interface::sample::LoopType;
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+
+pub fn write(
+    writer: &mut dyn Write,
+    audio: &Audio,
+    embed: &Embed,
+    tags: Option<&Tags>,
+) -> io::Result<()> {
+    let mut chunks = vec![fmt(audio)];
+
+    if let Data::Float(_) = audio.data {
+        chunks.push(fact(audio));
+    }
+
+    chunks.push(Chunk::new(b"data", audio.encode(Endian::Little, false)));
+
+    if embed.loop_points {
+        chunks.push(smpl(audio));
+    }
+
+    if let Some(tags) = tags {
+        chunks.push(info(tags));
+    }
+
+    write_container(writer, b"RIFF", b"WAVE", &chunks, Endian::Little)
+}
+
+fn fmt(audio: &Audio) -> Chunk {
+    let bits = audio.data.bits() as u16;
+    let block_align = audio.channels * (bits / 8);
+
+    let format = match audio.data {
+        Data::Int { .. } => WAVE_FORMAT_PCM,
+        Data::Float(_) => WAVE_FORMAT_IEEE_FLOAT,
+    };
+
+    let mut body = Vec::with_capacity(18);
+    body.extend_from_slice(&format.to_le_bytes());
+    body.extend_from_slice(&audio.channels.to_le_bytes());
+    body.extend_from_slice(&audio.rate.to_le_bytes());
+    body.extend_from_slice(&(audio.rate * block_align as u32).to_le_bytes());
+    body.extend_from_slice(&block_align.to_le_bytes());
+    body.extend_from_slice(&bits.to_le_bytes());
+
+    // Non-PCM formats must include the size of the extension
+    if format != WAVE_FORMAT_PCM {
+        body.extend_from_slice(&0_u16.to_le_bytes());
+    }
+
+    Chunk::new(b"fmt ", body)
+}
+
+fn fact(audio: &Audio) -> Chunk {
+    Chunk::new(b"fact", (audio.frames() as u32).to_le_bytes().to_vec())
+}
+
+/// Sampler chunk, stores the root note and loop points.
+fn smpl(audio: &Audio) -> Chunk {
+    let looping = audio.looping;
+
+    let loop_type: Option<u32> = match looping.kind() {
+        LoopType::Off => None,
+        LoopType::Forward => Some(0),
+        LoopType::PingPong => Some(1),
+        LoopType::Backward => Some(2),
+    };
+
+    let sample_period = match audio.rate {
+        0 => 0,
+        rate => 1_000_000_000 / rate,
+    };
+
+    let fields: [u32; 9] = [
+        0, // manufacturer
+        0, // product
+        sample_period,
+        ROOT_NOTE as u32,
+        0, // pitch fraction
+        0, // SMPTE format
+        0, // SMPTE offset
+        loop_type.is_some() as u32,
+        0, // sampler data
+    ];
+
+    let mut body: Vec<u8> = fields.iter().flat_map(|f| f.to_le_bytes()).collect();
+
+    if let Some(loop_type) = loop_type {
+        let fields: [u32; 6] = [
+            0, // cue point id
+            loop_type,
+            looping.start(),
+            looping.stop().saturating_sub(1), // inclusive
+            0,                                // fraction
+            0,                                // play count, 0 = infinite
+        ];
+
+        body.extend(fields.iter().flat_map(|f| f.to_le_bytes()));
+    }
+
+    Chunk::new(b"smpl", body)
+}
+
+/// ``LIST`` chunk containing ``INFO`` tags
+fn info(tags: &Tags) -> Chunk {
+    let fields: [(&[u8; 4], String); 5] = [
+        (b"INAM", tags.title().to_owned()),
+        (b"IPRD", tags.module_title.clone()),
+        (b"ITRK", tags.index_raw.to_string()),
+        (b"ICMT", tags.annotation()),
+        (b"ISFT", Tags::software()),
+    ];
+
+    let chunks: Vec<Chunk> = fields
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(id, value)| {
+            // Strings are null terminated
+            let mut value = value.into_bytes();
+            value.push(0);
+
+            Chunk::new(id, value)
+        })
+        .collect();
+
+    list(b"INFO", &chunks, Endian::Little)
+}
@@ -129,8 +129,8 @@ impl<'a> Display for Dump<'a> {
 /// Provide human friendly crash reporting
 pub fn set_panic_hook() {
     std::panic::set_hook(Box::new(move |panic_info| {
-        // Set stop_flag to abort to stop ripping process
-        stop_flag::set_abort();
+        // Abort the ripping job this thread was working on, other jobs carry on
+        stop_flag::abort_current();
 
         // gather necessary crash information
         let dump = Dump::from_panic(panic_info);
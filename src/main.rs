@@ -4,7 +4,9 @@
 pub mod app;
 mod cli;
 pub mod dialog;
+pub mod dsp;
 pub mod event;
+pub mod exporter;
 pub mod font;
 pub mod icon;
 pub mod logger;
@@ -31,9 +33,9 @@ fn main() -> iced::Result {
     logger::init_logging();
 
     match cli::parse(env::args().skip(1).collect()) {
-        Mode::None => XMODITS::launch(),
+        Mode::None(overrides) => XMODITS::launch(overrides),
         #[cfg(windows)]
-        Mode::DragNDrop(paths) => XMODITS::launch_simple(paths),
+        Mode::DragNDrop(paths, overrides) => XMODITS::launch_simple(paths, overrides),
         Mode::Version => cli::print_version(),
         Mode::Help => cli::print_help(),
         #[cfg(feature = "built")]
@@ -41,5 +43,9 @@ fn main() -> iced::Result {
         #[cfg(feature = "manual")]
         Mode::Manual => cli::print_manual(),
         Mode::Unrecognised(option) => cli::print_unrecognised(option),
+        Mode::InvalidFormat(format) => cli::print_invalid_format(format),
+        Mode::InvalidLog(log) => cli::print_invalid_log(log),
+        Mode::RetryWithPaths => cli::print_retry_with_paths(),
+        Mode::InvalidProfile(name) => cli::print_invalid_profile(name),
     }
 }
@@ -1,12 +1,16 @@
 //! The soul of XMODITS
 
+pub mod activity;
 pub mod extraction;
 pub mod handle;
+pub mod progress;
+pub mod queue;
 pub mod signal;
 pub mod stop_flag;
 pub mod subscription;
 
 pub use extraction::strict_loading;
 pub use handle::Handle;
+pub use queue::Queue;
 pub use signal::Signal;
 pub use subscription::{Message, subscription};
\ No newline at end of file
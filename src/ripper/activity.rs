@@ -0,0 +1,83 @@
+//! Modules that have recently been processed, shown while ripping.
+//!
+//! Only the latest entries are kept, so that large jobs don't hog memory.
+
+use std::collections::VecDeque;
+use std::path::Path;
+
+use super::extraction::error::Failed;
+use super::progress::Ripped;
+use crate::utils::filename;
+
+/// Maximum number of entries kept in the feed
+pub const CAPACITY: usize = 200;
+
+#[derive(Debug, Clone)]
+pub struct Activity {
+    pub module: String,
+    pub samples: u64,
+    /// Samples that were left out by the filters
+    pub skipped: usize,
+    /// Why the whole module was skipped
+    pub skipped_module: Option<String>,
+    /// Why the module (or some of its samples) failed to rip
+    pub error: Option<String>,
+}
+
+impl Activity {
+    pub fn new(module: &Path, ripped: &Ripped, error: Option<&Failed>) -> Self {
+        Self {
+            module: filename(module).to_owned(),
+            samples: ripped.samples,
+            skipped: ripped.skipped.len(),
+            skipped_module: ripped.skipped_module.clone(),
+            error: error.map(|failed| failed.reason.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for Activity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.error, &self.skipped_module, self.skipped) {
+            (Some(reason), _, 0) | (None, Some(reason), _) => {
+                write!(f, "{} - {}", self.module, reason)
+            }
+            // Skipped samples aren't part of the error
+            (Some(reason), _, skipped) => {
+                write!(f, "{} - {}, {} skipped", self.module, reason, skipped)
+            }
+            (None, None, 0) => write!(f, "{} - {} sample(s)", self.module, self.samples),
+            (None, None, skipped) => write!(
+                f,
+                "{} - {} sample(s), {} skipped",
+                self.module, self.samples, skipped
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Feed(VecDeque<Activity>);
+
+impl Feed {
+    pub fn push(&mut self, activity: Activity) {
+        if self.0.len() == CAPACITY {
+            self.0.pop_front();
+        }
+        self.0.push_back(activity);
+    }
+
+    /// Append the entries of another feed, keeping their order.
+    pub fn extend(&mut self, other: Feed) {
+        other.0.into_iter().for_each(|activity| self.push(activity));
+    }
+
+    /// Newest first
+    pub fn iter(&self) -> impl Iterator<Item = &Activity> {
+        self.0.iter().rev()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
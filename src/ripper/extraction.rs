@@ -1,18 +1,21 @@
 pub mod buffer;
 pub mod error;
 pub mod error_handler;
+pub mod filter;
+pub mod module;
 
 pub use buffer::{Batch, Buffer};
-pub use error::Failed;
+pub use error::{Failed, RipError};
 pub use error_handler::ErrorHandler;
+pub use module::ModuleRipper;
 
 use crate::logger;
 
-use super::stop_flag;
+use super::progress::Counters;
+use super::stop_flag::{StopFlag, StopToken};
 use super::Signal;
 
 use data::config::SampleRippingConfig;
-use xmodits_lib::Ripper;
 
 use std::fs::File;
 use std::io::{BufRead, BufReader, BufWriter, Seek, Write};
@@ -25,9 +28,9 @@ use walkdir::WalkDir;
 
 #[derive(Debug)]
 pub enum Message {
-    SetTotal(u64),
     Info(Option<String>),
-    Progress(Option<Failed>),
+    /// Progress is tracked with [Counters], only failures are sent.
+    Failed(Failed),
     Done,
     Stop(StopMessage),
 }
@@ -56,7 +59,7 @@ fn split_files_folders(paths: Vec<PathBuf>) -> (Vec<PathBuf>, Vec<PathBuf>) {
     (files, folders)
 }
 
-pub fn rip(tx: AsyncSender<Message>, signal: Signal) {
+pub fn rip(tx: AsyncSender<Message>, signal: Signal, counters: Counters) {
     let (files, folders) = split_files_folders(signal.entries);
 
     let mut cfg = signal.ripping;
@@ -66,21 +69,21 @@ pub fn rip(tx: AsyncSender<Message>, signal: Signal) {
         d => d,
     };
 
-    let ripper = Arc::new(Ripper::new(
-        signal.naming.build_func(),
-        cfg.exported_format.into(),
-    ));
+    let stop = signal.stop;
+    stop.register();
+
+    let ripper = Arc::new(ModuleRipper::new(&cfg, &signal.naming));
 
     // Create the destination folder if it doesn't exist
     let _ = std::fs::create_dir(&cfg.destination);
 
-    stage_1(tx.clone(), files, ripper.clone(), &cfg);
-    stage_2(tx.clone(), folders, ripper, cfg);
+    stage_1(tx.clone(), files, ripper.clone(), &cfg, &stop, &counters);
+    stage_2(tx.clone(), folders, ripper, cfg, &stop, &counters);
 
-    tx.send(match stop_flag::get_flag() {
-        stop_flag::StopFlag::None => Message::Done,
-        stop_flag::StopFlag::Cancel => Message::Stop(StopMessage::Cancel),
-        stop_flag::StopFlag::Abort => Message::Stop(StopMessage::Abort),
+    tx.send(match stop.get_flag() {
+        StopFlag::None => Message::Done,
+        StopFlag::Cancel => Message::Stop(StopMessage::Cancel),
+        StopFlag::Abort => Message::Stop(StopMessage::Abort),
     })
     .expect("Informing main GUI that the extraction has completed");
 }
@@ -88,15 +91,15 @@ pub fn rip(tx: AsyncSender<Message>, signal: Signal) {
 fn stage_1(
     subscr_tx: AsyncSender<Message>,
     files: Vec<PathBuf>,
-    ripper: Arc<Ripper>,
+    ripper: Arc<ModuleRipper>,
     cfg: &SampleRippingConfig,
+    stop: &StopToken,
+    counters: &Counters,
 ) {
     if files.is_empty() {
         return;
     }
-    subscr_tx
-        .send(Message::SetTotal(files.len() as u64))
-        .unwrap();
+    counters.set_total(files.len() as u64);
 
     let info = format!("Stage 1: Ripping {} files...", files.len());
     subscr_tx.send(Message::info(info)).unwrap();
@@ -104,15 +107,15 @@ fn stage_1(
     let filter = strict_loading(cfg.strict);
 
     for file in files.iter().filter(|f| filter(f)) {
-        if stop_flag::is_set() {
+        stop.wait_while_paused();
+
+        if stop.is_set() {
             break;
         }
 
-        let _ = subscr_tx.send(Message::Progress(
-            extract(file, &cfg.destination, ripper.as_ref(), cfg.self_contained)
-                .map_err(|error| Failed::new(file.display().to_string(), error))
-                .err(),
-        ));
+        if let Some(failed) = extract(file, &cfg.destination, ripper.as_ref(), counters) {
+            let _ = subscr_tx.send(Message::Failed(failed));
+        }
     }
 }
 
@@ -121,10 +124,12 @@ fn stage_1(
 fn stage_2(
     subscr_tx: AsyncSender<Message>,
     folders: Vec<PathBuf>,
-    ripper: Arc<Ripper>,
+    ripper: Arc<ModuleRipper>,
     cfg: SampleRippingConfig,
+    stop: &StopToken,
+    counters: &Counters,
 ) {
-    if folders.is_empty() || stop_flag::is_set() {
+    if folders.is_empty() || stop.is_set() {
         return;
     }
     let selected_dirs = folders.len();
@@ -134,12 +139,12 @@ fn stage_2(
 
     let filter = strict_loading(cfg.strict);
 
-    let (mut file, lines) = traverse(folders, cfg.folder_max_depth, filter, |lines| {
+    let (mut file, lines) = traverse(folders, cfg.folder_max_depth, filter, stop, |lines| {
         let info = format!("Traversing Directories...\n({lines} filtered files)");
         subscr_tx.send(Message::info(info)).unwrap()
     });
 
-    subscr_tx.send(Message::SetTotal(lines)).unwrap();
+    counters.set_total(lines);
 
     let plural = |n: u64| -> &str {
         if n > 1 {
@@ -156,11 +161,21 @@ fn stage_2(
     );
     subscr_tx.send(Message::info(info)).unwrap();
 
-    if stop_flag::is_set() {
+    if stop.is_set() {
         return;
     }
 
-    Batcher::new(&mut file, batch_size(lines), ripper, cfg, subscr_tx).start();
+    let batch_size = batch_size(lines);
+    Batcher::new(
+        &mut file,
+        batch_size,
+        ripper,
+        cfg,
+        subscr_tx,
+        stop.clone(),
+        counters.clone(),
+    )
+    .start();
 }
 
 fn batch_size(lines: u64) -> usize {
@@ -174,15 +189,22 @@ fn batch_size(lines: u64) -> usize {
     }
 }
 
+/// Rip a module and count it, returns why it failed.
 fn extract(
-    file: impl AsRef<Path>,
+    file: &Path,
     destination: &Path,
-    ripper: &Ripper,
-    self_contained: bool,
-) -> Result<(), xmodits_lib::Error> {
-    logger::log_file_on_panic(file.as_ref(), |file| {
-        xmodits_lib::extract(file, destination, ripper, self_contained)
-    })
+    ripper: &ModuleRipper,
+    counters: &Counters,
+) -> Option<Failed> {
+    let (ripped, result) =
+        logger::log_file_on_panic(file, |file| ripper.rip_counted(file, destination));
+
+    let failed = result
+        .err()
+        .map(|error| Failed::new(file.display().to_string(), error));
+
+    counters.push(file, &ripped, failed.as_ref());
+    failed
 }
 
 /// Traversing deeply nested directories can use a lot of memory.
@@ -192,6 +214,7 @@ fn traverse(
     dirs: Vec<PathBuf>,
     max_depth: u8,
     filter: impl Fn(&Path) -> bool,
+    stop: &StopToken,
     callback: impl Fn(u64),
 ) -> (BufReader<File>, u64) {
     let mut file = tempfile::tempfile()
@@ -207,7 +230,7 @@ fn traverse(
             .max_depth(max_depth as usize)
             .into_iter()
         {
-            if stop_flag::is_set() {
+            if stop.is_set() {
                 break 'traversal;
             }
 
@@ -251,15 +274,18 @@ struct Batcher<'io> {
     buffer: Buffer<String>,
     batch_tx: Sender<Batch<String>>,
     worker_rx: Receiver<NextBatch>,
+    stop: StopToken,
 }
 
 impl<'io> Batcher<'io> {
     fn new(
         file: &'io mut BufReader<File>,
         batch_size: usize,
-        ripper: Arc<Ripper>,
+        ripper: Arc<ModuleRipper>,
         cfg: SampleRippingConfig,
         subscr_tx: AsyncSender<Message>,
+        stop: StopToken,
+        counters: Counters,
     ) -> Batcher<'io> {
         let (batch_tx, batch_rx) = mpsc::channel::<Batch<String>>();
         let (worker_tx, worker_rx) = mpsc::channel::<NextBatch>();
@@ -272,6 +298,7 @@ impl<'io> Batcher<'io> {
             buffer: Buffer::init(batch_size),
             batch_tx,
             worker_rx,
+            stop: stop.clone(),
         };
 
         // load first buffer
@@ -282,27 +309,30 @@ impl<'io> Batcher<'io> {
             use rayon::prelude::*;
 
             let destination = cfg.destination;
-            let self_contained = cfg.self_contained;
+            let worker_stop = stop.clone();
 
             rayon::ThreadPoolBuilder::new()
                 .thread_name(|index| format!("XMODITS Ripping Thread - {index}"))
                 .num_threads(cfg.worker_threads)
+                .start_handler(move |_| worker_stop.register())
                 .panic_handler(|_| {/* Don't abort process */})
                 .build()
                 .expect("constructing thread pool")
                 .spawn(move || {
                     while let Ok(batch) = batch_rx.recv() {
                         batch.lock().par_iter().for_each(|file| {
-                            if stop_flag::is_set() {
+                            stop.wait_while_paused();
+
+                            if stop.is_set() {
                                 return;
                             }
 
-                            // Send an update to the subscription
-                            let _ = subscr_tx.send(Message::Progress(
-                                extract(file, &destination, &ripper, self_contained)
-                                    .err()
-                                    .map(|error| Failed::new(file.into(), error)),
-                            ));
+                            let file = Path::new(file);
+
+                            // Only failures need to reach the subscription
+                            if let Some(failed) = extract(file, &destination, &ripper, &counters) {
+                                let _ = subscr_tx.send(Message::Failed(failed));
+                            }
                         });
 
                         // Tell the batcher we're done so that it can send the next round
@@ -317,7 +347,7 @@ impl<'io> Batcher<'io> {
     pub fn start(&mut self) {
         let mut is_last_batch = false;
 
-        while !(self.state.complete || stop_flag::is_set()) {
+        while !(self.state.complete || self.stop.is_set()) {
             // If this is the last batch, set the state to complete
             // and send the last batch. When complete this loop terminates.
             self.state.complete = is_last_batch;
@@ -1,15 +1,129 @@
 use std::path::PathBuf;
 
-use xmodits_lib::Error;
+use serde::{Deserialize, Serialize};
+use xmodits_lib::Sample;
 
 use crate::utils::filename;
 
+/// Errors that can occur when ripping a module
+#[derive(Debug)]
+pub enum RipError {
+    Module(xmodits_lib::Error),
+    Io(std::io::Error),
+    NoSamples,
+    /// Some samples couldn't be ripped
+    Samples(Vec<SampleError>),
+}
+
+impl std::fmt::Display for RipError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Module(error) => write!(f, "{}", error),
+            Self::Io(error) => write!(f, "{}", error),
+            Self::NoSamples => write!(f, "Module has no samples"),
+            Self::Samples(errors) => {
+                write!(f, "{} sample(s) weren't ripped:", errors.len())?;
+                errors
+                    .iter()
+                    .try_for_each(|error| write!(f, "\n        {}", error))
+            }
+        }
+    }
+}
+
+impl From<xmodits_lib::Error> for RipError {
+    fn from(error: xmodits_lib::Error) -> Self {
+        Self::Module(error)
+    }
+}
+
+impl From<std::io::Error> for RipError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// A sample that couldn't be ripped, or was deliberately skipped
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampleError {
+    pub raw_index: usize,
+    pub name: String,
+    #[serde(rename = "message")]
+    pub reason: String,
+}
+
+impl SampleError {
+    pub fn new(smp: &Sample, reason: impl std::fmt::Display) -> Self {
+        Self {
+            raw_index: smp.index_raw() as usize,
+            name: smp.name_pretty().trim().to_owned(),
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn skipped(smp: &Sample, reason: impl std::fmt::Display) -> Self {
+        Self::new(smp, format_args!("Skipped: {}", reason))
+    }
+
+    /// Parse a line written by the [Display](std::fmt::Display) implementation
+    ///
+    /// The name ends at the first "): ", so names containing it are cut short.
+    pub fn parse(line: &str) -> Option<Self> {
+        let rest = line.strip_prefix('#')?;
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let raw_index = rest[..digits].parse().ok()?;
+        let rest = &rest[digits..];
+
+        let (name, reason) = match rest.strip_prefix(" (") {
+            Some(rest) => rest.split_once("): ")?,
+            None => ("", rest.strip_prefix(": ")?),
+        };
+
+        Some(Self {
+            raw_index,
+            name: name.to_owned(),
+            reason: reason.to_owned(),
+        })
+    }
+}
+
+impl std::fmt::Display for SampleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name.as_str() {
+            "" => write!(f, "#{}: {}", self.raw_index, self.reason),
+            name => write!(f, "#{} ({}): {}", self.raw_index, name, self.reason),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Failed {
     pub path: PathBuf,
+    pub kind: ErrorKind,
     pub reason: Reason,
 }
 
+/// What went wrong, mirrors [RipError]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    Module,
+    Io,
+    NoSamples,
+    Samples,
+}
+
+impl From<&RipError> for ErrorKind {
+    fn from(error: &RipError) -> Self {
+        match error {
+            RipError::Module(_) => Self::Module,
+            RipError::Io(_) => Self::Io,
+            RipError::NoSamples => Self::NoSamples,
+            RipError::Samples(_) => Self::Samples,
+        }
+    }
+}
+
 impl std::fmt::Display for Failed {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -24,24 +138,35 @@ impl std::fmt::Display for Failed {
 #[derive(Debug, Clone)]
 pub enum Reason {
     Single(String),
-    Multiple(Vec<(usize, String)>),
+    Multiple(Vec<SampleError>),
+}
+
+impl std::fmt::Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Single(reason) => write!(f, "{}", reason),
+            Self::Multiple(errors) => write!(f, "{} sample(s) weren't ripped", errors.len()),
+        }
+    }
 }
 
 impl Failed {
-    pub fn new(path: String, error: Error) -> Self {
+    pub fn new(path: String, error: RipError) -> Self {
         let path: PathBuf = path.into();
+        let kind = ErrorKind::from(&error);
         let reason = match error {
-            Error::FailedRip(multi) => Reason::Multiple(
-                multi
-                    .inner()
-                    .into_iter()
-                    .map(|reason| (reason.raw_index, reason.reason.to_string()))
-                    .collect(),
-            ),
+            RipError::Samples(errors) => Reason::Multiple(errors),
             single => Reason::Single(single.to_string()),
         };
 
-        Self { path, reason }
+        Self { path, kind, reason }
+    }
+
+    /// Ripping the module again could succeed with different settings.
+    ///
+    /// Modules without samples won't.
+    pub fn can_retry(&self) -> bool {
+        self.kind != ErrorKind::NoSamples
     }
 
     pub fn filename(&self) -> &str {
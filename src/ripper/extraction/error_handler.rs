@@ -1,9 +1,11 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+use data::config::general::ErrorLogFormat;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use tokio::io::{AsyncWriteExt, BufWriter};
 
-use super::error::{Failed, Reason};
+use super::error::{ErrorKind, Failed, Reason, SampleError};
 
 const MAX: usize = 100;
 const ABSOLUTE_LIMIT: usize = MAX * 10;
@@ -21,11 +23,13 @@ pub enum ErrorHandler {
     Mem {
         errors: Vec<Failed>,
         log_dir: PathBuf,
+        format: ErrorLogFormat,
     },
     File {
         total: u64,
         path: PathBuf,
         file: Box<BufWriter<tokio::fs::File>>,
+        format: ErrorLogFormat,
     },
     FailedFile {
         reason: String,
@@ -42,31 +46,38 @@ impl Default for ErrorHandler {
             errors: Vec::with_capacity(MAX + 1),
             log_dir: dirs::download_dir()
                 .unwrap_or_else(|| std::env::current_dir().unwrap_or_default()),
+            format: ErrorLogFormat::default(),
         }
     }
 }
 
 impl ErrorHandler {
-    pub fn new(log_dir: PathBuf) -> Self {
+    pub fn new(log_dir: PathBuf, format: ErrorLogFormat) -> Self {
         Self::Mem {
             errors: Vec::with_capacity(MAX + 1),
             log_dir,
+            format,
         }
     }
 
     pub async fn push(&mut self, error: Failed) {
         match self {
-            ErrorHandler::Mem { errors, log_dir } => {
+            ErrorHandler::Mem {
+                errors,
+                log_dir,
+                format,
+            } => {
                 if errors.len() < MAX {
                     errors.push(error);
                     return;
                 }
 
+                let format = *format;
                 let mut errors = std::mem::take(errors);
                 let mut log_path = std::mem::take(log_dir);
 
                 errors.push(error);
-                log_path.push(random_name());
+                log_path.push(random_name(format));
 
                 *self = match tokio::fs::OpenOptions::new()
                     .write(true)
@@ -81,13 +92,14 @@ impl ErrorHandler {
 
                         // Write stored errors to the new file
                         for error in errors {
-                            Self::write_error(&mut file, error).await;
+                            Self::write_error(&mut file, error, format).await;
                         }
 
                         Self::File {
                             total,
                             path: log_path,
                             file,
+                            format,
                         }
                     }
 
@@ -99,8 +111,13 @@ impl ErrorHandler {
                 };
             }
 
-            ErrorHandler::File { total, file, .. } => {
-                Self::write_error(file, error).await;
+            ErrorHandler::File {
+                total,
+                file,
+                format,
+                ..
+            } => {
+                Self::write_error(file, error, *format).await;
                 *total += 1;
             }
 
@@ -114,7 +131,11 @@ impl ErrorHandler {
     }
 
     /// dump the errors to a file, will overwrite
-    pub async fn dump(errors: Vec<Failed>, path: PathBuf) -> Result<PathBuf, String> {
+    pub async fn dump(
+        errors: Vec<Failed>,
+        path: PathBuf,
+        format: ErrorLogFormat,
+    ) -> Result<PathBuf, String> {
         match tokio::fs::OpenOptions::new()
             .write(true)
             .truncate(true)
@@ -125,7 +146,7 @@ impl ErrorHandler {
         {
             Ok(mut file) => {
                 for error in errors {
-                    Self::write_error(&mut file, error).await;
+                    Self::write_error(&mut file, error, format).await;
                 }
                 Ok(path)
             }
@@ -133,34 +154,149 @@ impl ErrorHandler {
         }
     }
 
-    async fn write_error<W>(file: &mut W, error: Failed)
+    /// Read the errors back from a log file written in either format.
+    pub async fn read(path: PathBuf) -> Result<Vec<Failed>, String> {
+        let contents = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|error| error.to_string())?;
+
+        let errors = match contents.trim_start().starts_with('{') {
+            true => contents
+                .lines()
+                .filter_map(|line| serde_json::from_str::<LogEntry>(line).ok())
+                .map(Failed::from)
+                .collect(),
+            false => parse_text(&contents),
+        };
+
+        Ok(errors)
+    }
+
+    async fn write_error<W>(file: &mut W, error: Failed, format: ErrorLogFormat)
     where
         W: AsyncWriteExt + std::marker::Unpin,
     {
-        let failed_file = error.path.display().to_string();
-        let _ = file.write_all(failed_file.as_bytes()).await;
-        let _ = file.write_all(b"\n     ").await;
+        let entry = match format {
+            ErrorLogFormat::Text => format_text(&error),
+            ErrorLogFormat::JsonLines => format_json_line(&error),
+        };
 
-        match error.reason {
-            Reason::Single(reason) => {
-                let _ = file.write_all(reason.as_bytes()).await;
-            }
-            Reason::Multiple(reasons) => {
-                //todo include raw index
-                for (_raw_idx, reason) in reasons {
-                    let _ = file.write_all(reason.as_bytes()).await;
-                    let _ = file.write_all(b"\n").await;
+        let _ = file.write_all(entry.as_bytes()).await;
+        let _ = file.flush().await;
+    }
+}
+
+/// ```text
+/// path/to/module.it
+///     #1 (kick): reason
+///     #4: reason
+/// ```
+fn format_text(error: &Failed) -> String {
+    let mut entry = format!("{}\n", error.path.display());
+
+    match &error.reason {
+        Reason::Single(reason) => entry.push_str(&format!("    {}\n", reason)),
+        Reason::Multiple(errors) => errors
+            .iter()
+            .for_each(|error| entry.push_str(&format!("    {}\n", error))),
+    }
+
+    entry.push('\n');
+    entry
+}
+
+/// Text logs are meant to be read by people, so reading them back is lossy:
+/// the kind of error has to be guessed, and sample names containing "): " aren't split correctly.
+///
+/// JSON Lines logs are read back exactly.
+fn parse_text(contents: &str) -> Vec<Failed> {
+    contents
+        .split("\n\n")
+        .filter_map(|entry| {
+            let mut lines = entry.lines().filter(|line| !line.trim().is_empty());
+            let path = lines.next()?;
+            let lines: Vec<&str> = lines.map(str::trim).collect();
+
+            let samples: Option<Vec<SampleError>> =
+                lines.iter().map(|line| SampleError::parse(line)).collect();
+
+            let (kind, reason) = match samples {
+                Some(samples) if !samples.is_empty() => {
+                    (ErrorKind::Samples, Reason::Multiple(samples))
                 }
-            }
+                _ => {
+                    let reason = lines.join("\n");
+                    (guess_kind(&reason), Reason::Single(reason))
+                }
+            };
+
+            Some(Failed {
+                path: path.into(),
+                kind,
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// Text logs don't store the kind of error.
+///
+/// Anything that isn't recognised is treated as a module error.
+fn guess_kind(reason: &str) -> ErrorKind {
+    match reason {
+        "Module has no samples" => ErrorKind::NoSamples,
+        _ => ErrorKind::Module,
+    }
+}
+
+/// A single line of a JSON Lines error log
+#[derive(Serialize, Deserialize)]
+struct LogEntry {
+    path: String,
+    kind: ErrorKind,
+    message: String,
+    #[serde(default)]
+    samples: Vec<SampleError>,
+}
+
+impl From<LogEntry> for Failed {
+    fn from(entry: LogEntry) -> Self {
+        Self {
+            path: entry.path.into(),
+            kind: entry.kind,
+            reason: match entry.samples.is_empty() {
+                true => Reason::Single(entry.message),
+                false => Reason::Multiple(entry.samples),
+            },
         }
-        let _ = file.write_all(b"\n\n").await;
-        let _ = file.flush().await;
     }
 }
 
-pub fn random_name() -> String {
+fn format_json_line(error: &Failed) -> String {
+    let entry = LogEntry {
+        path: error.path.display().to_string(),
+        kind: error.kind,
+        message: error.reason.to_string(),
+        samples: match &error.reason {
+            Reason::Single(_) => Vec::new(),
+            Reason::Multiple(errors) => errors.clone(),
+        },
+    };
+
+    let mut line = serde_json::to_string(&entry).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+pub fn random_name(format: ErrorLogFormat) -> String {
     format!(
-        "xmodits-error-log-{:04X}.txt",
-        rand::thread_rng().gen::<u16>()
+        "xmodits-error-log-{:04X}.{}",
+        rand::thread_rng().gen::<u16>(),
+        format.extension()
     )
 }
+
+/// Error logs are written to the logging path if it's set, otherwise the destination.
+pub fn log_dir(logging_path: Option<&Path>, destination: &Path) -> PathBuf {
+    logging_path.unwrap_or(destination).to_path_buf()
+}
@@ -0,0 +1,205 @@
+//! Decide which samples of a module shouldn't be ripped
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+use data::config::sample_ripping::StaticNoise;
+use data::config::SampleRippingConfig;
+use xmodits_lib::dsp::{RawSample, SampleBuffer};
+use xmodits_lib::{Module, Sample};
+
+use crate::dsp::noise::{self, Analysis};
+use crate::dsp::{db_to_amplitude, peak};
+
+/// Why a sample was skipped
+#[derive(Debug, Clone, Copy)]
+pub enum Skip {
+    /// Length in frames
+    TooShort(usize),
+    /// Peak amplitude
+    Silent(f32),
+    StaticNoise(Analysis),
+    /// Same PCM data as the sample with this raw index
+    Duplicate(usize),
+}
+
+impl std::fmt::Display for Skip {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TooShort(frames) => write!(f, "too short ({} frames)", frames),
+            Self::Silent(peak) if *peak == 0.0 => write!(f, "silent (every frame is zero)"),
+            Self::Silent(peak) => write!(f, "silent (peak {:.1} dB)", 20.0 * peak.log10()),
+            Self::StaticNoise(analysis) => write!(f, "static noise ({})", analysis),
+            Self::Duplicate(of) => write!(f, "duplicate of #{}", of),
+        }
+    }
+}
+
+pub struct Filter {
+    static_noise: StaticNoise,
+    min_frames: usize,
+    /// Samples whose peak stays below this amplitude are skipped
+    silence: Option<f32>,
+    duplicates: bool,
+}
+
+impl Filter {
+    pub fn new(cfg: &SampleRippingConfig) -> Self {
+        Self {
+            static_noise: cfg.static_noise,
+            min_frames: cfg.min_frames as usize,
+            silence: cfg
+                .skip_silent
+                .then(|| db_to_amplitude(cfg.silence_threshold)),
+            duplicates: cfg.skip_duplicates,
+        }
+    }
+
+    /// Why the whole module should be skipped, if more than half of its samples are static noise.
+    ///
+    /// Takes what [Filter::screen] found for each sample.
+    pub fn skips_module(&self, skipped: &[Option<Skip>]) -> Option<String> {
+        if self.static_noise != StaticNoise::SkipModules {
+            return None;
+        }
+
+        let flagged = skipped
+            .iter()
+            .filter(|skip| matches!(skip, Some(Skip::StaticNoise(_))))
+            .count();
+        let total = skipped.len();
+
+        (flagged * 2 > total).then(|| {
+            format!(
+                "Skipped: {} of {} samples are most likely static noise",
+                flagged, total
+            )
+        })
+    }
+
+    /// Whether the samples have to be decoded to decide if they should be skipped.
+    fn decodes(&self) -> bool {
+        self.static_noise != StaticNoise::Keep || self.silence.is_some() || self.duplicates
+    }
+
+    /// Find out why each of the module's samples should be skipped, in the same order.
+    ///
+    /// Samples that can't be read are left for the ripper to report.
+    pub fn screen(&self, module: &dyn Module) -> Vec<Option<Skip>> {
+        let mut originals: HashMap<u64, Vec<&Sample>> = HashMap::new();
+
+        module
+            .samples()
+            .iter()
+            .map(|smp| {
+                let frames = smp.length_frames();
+
+                if frames < self.min_frames {
+                    return Some(Skip::TooShort(frames));
+                }
+
+                if !self.decodes() {
+                    return None;
+                }
+
+                let pcm = module.pcm(smp).ok()?;
+
+                if self.silence.is_some() || self.static_noise != StaticNoise::Keep {
+                    let buf = SampleBuffer::from(RawSample::new(smp, pcm.clone())).buf;
+                    let peak = peak(&buf);
+
+                    if self.silence.is_some_and(|threshold| peak < threshold) {
+                        return Some(Skip::Silent(peak));
+                    }
+
+                    if self.static_noise != StaticNoise::Keep {
+                        let analysis = noise::analyse(&buf);
+
+                        if analysis.is_noise() {
+                            return Some(Skip::StaticNoise(analysis));
+                        }
+                    }
+                }
+
+                if !self.duplicates {
+                    return None;
+                }
+
+                // Only samples that will be ripped can be duplicated
+                let candidates = originals.entry(fingerprint(smp, &pcm)).or_default();
+
+                let original = candidates.iter().find(|original| {
+                    same_format(original, smp)
+                        && module.pcm(original).is_ok_and(|other| other == pcm)
+                });
+
+                match original {
+                    Some(original) => Some(Skip::Duplicate(original.index_raw() as usize)),
+                    None => {
+                        candidates.push(smp);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// The PCM data is only identical if it's stored the same way.
+fn same_format(a: &Sample, b: &Sample) -> bool {
+    a.bits() == b.bits() && a.is_signed() == b.is_signed() && a.is_stereo() == b.is_stereo()
+}
+
+fn fingerprint(smp: &Sample, pcm: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    (smp.bits(), smp.is_signed(), smp.is_stereo()).hash(&mut hasher);
+    pcm.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(static_noise: StaticNoise) -> Filter {
+        Filter::new(&SampleRippingConfig {
+            static_noise,
+            ..Default::default()
+        })
+    }
+
+    fn noise() -> Option<Skip> {
+        Some(Skip::StaticNoise(Analysis::default()))
+    }
+
+    #[test]
+    fn skips_module_when_more_than_half_is_noise() {
+        let filter = filter(StaticNoise::SkipModules);
+
+        assert!(filter.skips_module(&[noise(), None]).is_none());
+        assert!(filter
+            .skips_module(&[noise(), noise(), None, None])
+            .is_none());
+        assert!(filter.skips_module(&[noise(), noise(), None]).is_some());
+        assert!(filter.skips_module(&[noise()]).is_some());
+    }
+
+    #[test]
+    fn other_skips_dont_count_as_noise() {
+        let filter = filter(StaticNoise::SkipModules);
+        let skipped = [noise(), Some(Skip::TooShort(1)), Some(Skip::Silent(0.0))];
+
+        assert!(filter.skips_module(&skipped).is_none());
+    }
+
+    #[test]
+    fn only_skips_modules_when_enabled() {
+        let skipped = [noise(), noise(), noise()];
+
+        assert!(filter(StaticNoise::Keep).skips_module(&skipped).is_none());
+        assert!(filter(StaticNoise::SkipSamples)
+            .skips_module(&skipped)
+            .is_none());
+    }
+}
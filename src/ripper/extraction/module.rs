@@ -0,0 +1,450 @@
+//! Rip the samples of a single module
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use data::config::sample_ripping::Sidecar;
+use data::config::{SampleNameConfig, SampleRippingConfig};
+use xmodits_lib::interface::name::Context;
+use xmodits_lib::{Module, Sample, SampleNamerTrait};
+
+use crate::exporter::sf2::SoundFontSample;
+use crate::exporter::sidecar::{self, SampleMetadata};
+use crate::exporter::tags::ModuleInfo;
+use crate::exporter::{sf2, sfz, Exported, Exporter, Side, Written};
+use crate::ripper::progress::Ripped;
+use crate::utils::filename;
+
+use super::error::{RipError, SampleError};
+use super::filter::{Filter, Skip};
+
+pub struct ModuleRipper {
+    namer: Box<dyn SampleNamerTrait>,
+    exporter: Exporter,
+    self_contained: bool,
+    generate_sfz: bool,
+    generate_sf2: bool,
+    sf2_sample_presets: bool,
+    sidecar: Sidecar,
+    filter: Filter,
+}
+
+impl ModuleRipper {
+    pub fn new(ripping: &SampleRippingConfig, naming: &SampleNameConfig) -> Self {
+        Self {
+            namer: naming.build_func(),
+            exporter: Exporter::new(ripping),
+            self_contained: ripping.self_contained,
+            generate_sfz: ripping.generate_sfz,
+            generate_sf2: ripping.generate_sf2,
+            sf2_sample_presets: ripping.sf2_sample_presets,
+            sidecar: ripping.sidecar,
+            filter: Filter::new(ripping),
+        }
+    }
+
+    /// Load the module and write its samples to ``destination``.
+    ///
+    /// Samples that couldn't be ripped won't stop the rest from being ripped.
+    pub fn rip(&self, path: &Path, destination: &Path) -> Result<(), RipError> {
+        self.rip_counted(path, destination).1
+    }
+
+    /// Same as [rip](Self::rip), but also counts what was written,
+    /// including the samples of modules that didn't rip completely.
+    pub fn rip_counted(&self, path: &Path, destination: &Path) -> (Ripped, Result<(), RipError>) {
+        let mut ripped = Ripped::default();
+        let result = self.rip_module(path, destination, &mut ripped);
+        (ripped, result)
+    }
+
+    fn rip_module(
+        &self,
+        path: &Path,
+        destination: &Path,
+        ripped: &mut Ripped,
+    ) -> Result<(), RipError> {
+        let mut file = File::open(path)?;
+        let module = xmodits_lib::load_module(&mut file)?;
+
+        if module.samples().is_empty() {
+            return Err(RipError::NoSamples);
+        }
+
+        let skipped = self.filter.screen(&*module);
+
+        if let Some(reason) = self.filter.skips_module(&skipped) {
+            tracing::info!("{}: {}", path.display(), reason);
+            ripped.skipped_module = Some(reason);
+
+            return Ok(());
+        }
+
+        // Skipped samples aren't errors, they're reported separately.
+        ripped.skipped = module
+            .samples()
+            .iter()
+            .zip(&skipped)
+            .filter_map(|(smp, skip)| skip.map(|skip| SampleError::skipped(smp, skip)))
+            .collect();
+
+        for skip in &ripped.skipped {
+            tracing::info!("{}: {}", path.display(), skip);
+        }
+
+        // Don't leave an empty folder behind
+        if skipped.iter().all(Option::is_some) {
+            return Ok(());
+        }
+
+        let folder = match self.self_contained {
+            true => destination.join(folder_name(path)),
+            false => destination.to_owned(),
+        };
+
+        // Ripping the same module again reuses its folder, and overwrites its samples.
+        if self.self_contained {
+            std::fs::create_dir_all(&folder)?;
+        }
+
+        let info = ModuleInfo {
+            title: module.name().trim().to_owned(),
+            format: module.format().to_owned(),
+            path: path.to_owned(),
+        };
+
+        let (exported, errors) = self.rip_samples(&*module, &folder, &info, &skipped, ripped);
+
+        let instruments = self.write_instruments(&*module, path, &folder, &info, &exported);
+        let metadata = self.write_metadata(path, &folder, &info, &exported);
+        let written = instruments.and(metadata);
+
+        if !errors.is_empty() {
+            // Only one error can be reported, the samples that failed are more useful.
+            if let Err(error) = &written {
+                tracing::error!("{}: {}", path.display(), error);
+            }
+
+            return Err(RipError::Samples(errors));
+        }
+
+        written.map_err(RipError::from)
+    }
+
+    fn rip_samples(
+        &self,
+        module: &dyn Module,
+        folder: &Path,
+        info: &ModuleInfo,
+        skipped: &[Option<Skip>],
+        ripped: &mut Ripped,
+    ) -> (Vec<Exported>, Vec<SampleError>) {
+        let samples = module.samples();
+        let source_path: PathBuf = info.path.clone();
+
+        let context = Context {
+            total: samples.len(),
+            extension: self.exporter.extension(),
+            highest: samples
+                .iter()
+                .map(|smp| smp.index_raw() as usize)
+                .max()
+                .unwrap_or_default(),
+            source_path: Some(&source_path),
+        };
+
+        let mut exported = Vec::with_capacity(samples.len());
+        let mut errors = Vec::new();
+
+        for (seq_index, smp) in samples.iter().enumerate() {
+            if skipped[seq_index].is_some() {
+                continue;
+            }
+
+            let name = (self.namer)(smp, &context, seq_index);
+
+            let outputs: Vec<(PathBuf, Option<Side>)> = match self.exporter.splits(smp) {
+                true => Side::BOTH
+                    .iter()
+                    .map(|side| (folder.join(side.rename(&name)), Some(*side)))
+                    .collect(),
+                false => vec![(folder.join(&name), None)],
+            };
+
+            for (path, side) in outputs {
+                match self.rip_sample(module, smp, &path, info, side) {
+                    Ok((written, bytes)) => {
+                        ripped.samples += 1;
+                        ripped.bytes += bytes;
+
+                        exported.push(Exported {
+                            path,
+                            sample: smp.clone(),
+                            written,
+                            side,
+                        })
+                    }
+                    Err(reason) => {
+                        errors.push(SampleError::new(smp, reason));
+                        break;
+                    }
+                }
+            }
+        }
+
+        (exported, errors)
+    }
+
+    /// Write instruments that use the exported samples.
+    fn write_instruments(
+        &self,
+        module: &dyn Module,
+        path: &Path,
+        folder: &Path,
+        info: &ModuleInfo,
+        exported: &[Exported],
+    ) -> io::Result<()> {
+        if exported.is_empty() {
+            return Ok(());
+        }
+
+        if self.generate_sfz {
+            let sfz_path = folder.join(format!("{}.sfz", folder_name(path)));
+            let mut file = BufWriter::new(File::create(sfz_path)?);
+            sfz::write(&mut file, info, exported)?;
+        }
+
+        if self.generate_sf2 {
+            // SoundFonts link the sides of a stereo sample themselves
+            let samples: Vec<SoundFontSample> = exported
+                .iter()
+                .filter(|exported| exported.side != Some(Side::Right))
+                .filter_map(|exported| {
+                    let smp = &exported.sample;
+                    let pcm = module.pcm(smp).ok()?;
+
+                    Some(SoundFontSample {
+                        name: sample_name(smp),
+                        audio: self.exporter.render_as(smp, pcm, Some(16)),
+                    })
+                })
+                .collect();
+
+            let sf2_path = folder.join(format!("{}.sf2", folder_name(path)));
+            let mut file = BufWriter::new(File::create(sf2_path)?);
+            sf2::write(&mut file, info, &samples, self.sf2_sample_presets)?;
+        }
+
+        Ok(())
+    }
+
+    fn rip_sample(
+        &self,
+        module: &dyn Module,
+        smp: &Sample,
+        path: &Path,
+        info: &ModuleInfo,
+        side: Option<Side>,
+    ) -> Result<(Written, u64), xmodits_lib::Error> {
+        let pcm = module.pcm(smp)?;
+        let mut file = BufWriter::new(File::create(path)?);
+
+        let written = self.exporter.write(smp, pcm, info, side, &mut file)?;
+        file.flush()?;
+
+        let bytes = file
+            .get_ref()
+            .metadata()
+            .map(|m| m.len())
+            .unwrap_or_default();
+
+        Ok((written, bytes))
+    }
+
+    /// Write JSON files describing the exported samples.
+    fn write_metadata(
+        &self,
+        path: &Path,
+        folder: &Path,
+        info: &ModuleInfo,
+        exported: &[Exported],
+    ) -> io::Result<()> {
+        let metadata = || {
+            exported
+                .iter()
+                .map(|exported| SampleMetadata::new(info, exported, self.exporter.format()))
+        };
+
+        match self.sidecar {
+            Sidecar::Off => Ok(()),
+            Sidecar::PerSample => {
+                exported
+                    .iter()
+                    .zip(metadata())
+                    .try_for_each(|(exported, metadata)| {
+                        sidecar::write_sample(&metadata, &exported.path)
+                    })
+            }
+            Sidecar::PerModule if exported.is_empty() => Ok(()),
+            Sidecar::PerModule => {
+                // Modules share the destination folder if they're not self contained
+                let name = match self.self_contained {
+                    true => "samples.json".to_owned(),
+                    false => format!("{}_samples.json", folder_name(path)),
+                };
+
+                let metadata: Vec<SampleMetadata> = metadata().collect();
+                sidecar::write_module(info, &metadata, &folder.join(name))
+            }
+        }
+    }
+}
+
+fn sample_name(smp: &Sample) -> String {
+    match smp.name_pretty().trim() {
+        "" => format!("Sample {}", smp.index_raw()),
+        name => name.to_owned(),
+    }
+}
+
+/// Self contained folders are named after the module's filename, with '.' replaced with '_'.
+pub fn folder_name(path: &Path) -> String {
+    filename(path).replace('.', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use data::config::sample_ripping::StaticNoise;
+
+    use super::*;
+
+    const LENGTH: usize = 64;
+
+    fn sine(length: usize) -> Vec<i8> {
+        (0..length)
+            .map(|i| ((i as f32 / LENGTH as f32 * std::f32::consts::TAU).sin() * 100.0) as i8)
+            .collect()
+    }
+
+    fn white_noise(length: usize) -> Vec<i8> {
+        let mut seed = 0x1234_5678_u32;
+
+        (0..length)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 24) as i8
+            })
+            .collect()
+    }
+
+    /// A ProTracker module with two short samples, a sine and a square wave.
+    fn fixture() -> Vec<u8> {
+        let square: Vec<i8> = (0..LENGTH)
+            .map(|i| match i < LENGTH / 2 {
+                true => 100,
+                false => -100,
+            })
+            .collect();
+
+        protracker(&[sine(LENGTH), square])
+    }
+
+    /// A ProTracker module with the given 8 bit samples, they must have an even length.
+    fn protracker(samples: &[Vec<i8>]) -> Vec<u8> {
+        let mut module = Vec::new();
+        module.extend(b"fixture".iter().copied().chain([0; 13]));
+
+        for index in 0..31 {
+            let mut name = format!("sample {}", index + 1).into_bytes();
+            name.resize(22, 0);
+            module.extend(name);
+
+            let words = samples.get(index).map_or(0, |smp| (smp.len() / 2) as u16);
+            module.extend(words.to_be_bytes());
+            module.extend([0, 64]); // finetune, volume
+            module.extend(0_u16.to_be_bytes()); // loop start
+            module.extend(1_u16.to_be_bytes()); // loop length, 1 = no loop
+        }
+
+        module.extend([1, 127]); // song length, restart position
+        module.extend([0; 128]); // orders
+        module.extend(b"M.K.");
+        module.extend([0; 1024]); // a single empty pattern
+        module.extend(samples.iter().flatten().map(|byte| *byte as u8));
+        module
+    }
+
+    /// Rip the module, also returns whether its folder was created.
+    fn rip(module: &[u8], ripping: &SampleRippingConfig) -> (Ripped, bool) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.mod");
+        std::fs::write(&path, module).unwrap();
+
+        let ripper = ModuleRipper::new(ripping, &SampleNameConfig::default());
+        let (ripped, result) = ripper.rip_counted(&path, dir.path());
+        result.unwrap();
+
+        let created = dir.path().join(folder_name(&path)).exists();
+        (ripped, created)
+    }
+
+    #[test]
+    fn rips_module_again_into_same_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.mod");
+        std::fs::write(&path, fixture()).unwrap();
+
+        let destination = dir.path().join("ripped");
+        std::fs::create_dir(&destination).unwrap();
+
+        let ripper = ModuleRipper::new(
+            &SampleRippingConfig::default(),
+            &SampleNameConfig::default(),
+        );
+
+        for _ in 0..2 {
+            let (ripped, result) = ripper.rip_counted(&path, &destination);
+            result.unwrap();
+            assert_eq!(ripped.samples, 2);
+        }
+
+        let files = std::fs::read_dir(destination.join(folder_name(&path)))
+            .unwrap()
+            .count();
+        assert_eq!(files, 2);
+    }
+
+    #[test]
+    fn skips_noisy_samples() {
+        let module = protracker(&[white_noise(4096), sine(4096), white_noise(4096)]);
+        let ripping = SampleRippingConfig {
+            static_noise: StaticNoise::SkipSamples,
+            ..Default::default()
+        };
+
+        let (ripped, _) = rip(&module, &ripping);
+        assert_eq!(ripped.samples, 1);
+        assert_eq!(ripped.skipped.len(), 2);
+        assert!(ripped.skipped_module.is_none());
+    }
+
+    #[test]
+    fn skips_module_when_most_samples_are_noise() {
+        let module = protracker(&[white_noise(4096), sine(4096), white_noise(4096)]);
+        let ripping = SampleRippingConfig {
+            static_noise: StaticNoise::SkipModules,
+            ..Default::default()
+        };
+
+        let (ripped, created) = rip(&module, &ripping);
+        assert_eq!(ripped.samples, 0);
+        assert!(ripped.skipped_module.is_some());
+        assert!(!created);
+
+        let module = protracker(&[white_noise(4096), sine(4096)]);
+        let (ripped, _) = rip(&module, &ripping);
+        assert_eq!(ripped.samples, 1);
+        assert!(ripped.skipped_module.is_none());
+    }
+}
@@ -1,19 +1,21 @@
 use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::Sender;
 
-use super::{stop_flag, Signal};
+use super::stop_flag::StopToken;
+use super::Signal;
 
 /// Communicates with the subscription.
 ///
-/// Also provides convenience methods to access the global stop_flag.
+/// Also provides convenience methods to stop or pause the job that was sent last.
 #[derive(Default)]
 pub struct Handle {
     sender: Option<Sender<Signal>>,
+    stop: StopToken,
 }
 
 impl Handle {
     pub fn new() -> Self {
-        Self { sender: None }
+        Self::default()
     }
 
     pub fn set_sender(&mut self, sender: Sender<Signal>) {
@@ -21,7 +23,7 @@ impl Handle {
         self.sender = Some(sender)
     }
 
-    pub fn send(&self, signal: Signal) -> Result<(), Signal> {
+    pub fn send(&mut self, signal: Signal) -> Result<(), Signal> {
         let Some(sender) = self.sender.as_ref() else {
             return Err(signal);
         };
@@ -32,7 +34,10 @@ impl Handle {
             }
         };
 
-        sender.try_send(signal).map_err(get_sender)
+        let stop = signal.stop.clone();
+        sender.try_send(signal).map_err(get_sender)?;
+        self.stop = stop;
+        Ok(())
     }
 
     pub fn is_active(&self) -> bool {
@@ -41,8 +46,17 @@ impl Handle {
             .is_some_and(|sender| !sender.is_closed())
     }
 
-    /// Cancel the ripping process by setting the stop_flag to Cancel
+    /// Cancel the ripping process by setting its stop flag to Cancel
     pub fn cancel(&self) {
-        stop_flag::set_cancel()
+        self.stop.set_cancel()
+    }
+
+    /// Workers will finish the module they're ripping, then wait until resumed.
+    pub fn pause(&self) {
+        self.stop.set_paused(true)
+    }
+
+    pub fn resume(&self) {
+        self.stop.set_paused(false)
     }
 }
@@ -0,0 +1,273 @@
+//! Live statistics of a running job: throughput, bytes written and an estimated time remaining.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use super::activity::{Activity, Feed};
+use super::extraction::error::{Failed, SampleError};
+
+/// How often the rates are measured
+const INTERVAL: Duration = Duration::from_millis(500);
+
+/// Weight of the newest measurement in the moving average.
+const SMOOTHING: f32 = 0.3;
+
+/// What has been ripped from a module, even if some of its samples couldn't be ripped.
+#[derive(Debug, Default, Clone)]
+pub struct Ripped {
+    pub samples: u64,
+    pub bytes: u64,
+    /// Samples that were left out by the filters, these aren't errors.
+    pub skipped: Vec<SampleError>,
+    /// Why the whole module was left out by the filters
+    pub skipped_module: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Stats {
+    /// Modules processed in the current stage
+    pub processed: u64,
+    /// Modules to process in the current stage
+    pub total: u64,
+    pub errors: u64,
+    pub samples: u64,
+    /// Samples left out by the filters
+    pub skipped: u64,
+    pub bytes: u64,
+    pub elapsed: Duration,
+    pub modules_per_sec: f32,
+    pub samples_per_sec: f32,
+    pub eta: Option<Duration>,
+}
+
+impl Stats {
+    /// Between 0.0 and 100.0
+    pub fn percentage(&self) -> f32 {
+        match self.total {
+            0 => 0.0,
+            total => (self.processed as f32 / total as f32) * 100.0,
+        }
+    }
+
+    pub fn megabytes(&self) -> f32 {
+        self.bytes as f32 / 1_000_000.0
+    }
+
+    /// ```text
+    /// 12/400 modules - 3.2 modules/s - 41.0 samples/s
+    /// ```
+    pub fn throughput(&self) -> String {
+        format!(
+            "{}/{} modules - {:.1} modules/s - {:.1} samples/s",
+            self.processed, self.total, self.modules_per_sec, self.samples_per_sec
+        )
+    }
+
+    /// ```text
+    /// 21.4 MB written - Elapsed: 1m 04s - ETA: 2m 10s
+    /// ```
+    pub fn timing(&self) -> String {
+        format!(
+            "{:.1} MB written - Elapsed: {} - ETA: {}",
+            self.megabytes(),
+            hms(self.elapsed),
+            self.eta_text()
+        )
+    }
+
+    pub fn eta_text(&self) -> String {
+        match self.eta {
+            Some(eta) => hms(eta),
+            None => "--".to_owned(),
+        }
+    }
+}
+
+/// Progress of a job, shared between the workers and the subscription.
+///
+/// Workers only bump a few atomics for every module, the subscription samples them at a fixed rate.
+/// This keeps the overhead low even for jobs with hundreds of thousands of files.
+#[derive(Debug, Clone, Default)]
+pub struct Counters(Arc<Inner>);
+
+#[derive(Debug, Default)]
+struct Inner {
+    processed: AtomicU64,
+    total: AtomicU64,
+    errors: AtomicU64,
+    samples: AtomicU64,
+    skipped: AtomicU64,
+    bytes: AtomicU64,
+    /// Recently processed modules, drained by the subscription
+    recent: Mutex<Feed>,
+}
+
+impl Counters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A new stage has started with a different number of modules
+    pub fn set_total(&self, total: u64) {
+        self.0.total.store(total, Ordering::Relaxed);
+        self.0.processed.store(0, Ordering::Relaxed);
+    }
+
+    /// A module has been processed
+    pub fn push(&self, module: &Path, ripped: &Ripped, error: Option<&Failed>) {
+        self.0.processed.fetch_add(1, Ordering::Relaxed);
+        self.0.samples.fetch_add(ripped.samples, Ordering::Relaxed);
+        self.0
+            .skipped
+            .fetch_add(ripped.skipped.len() as u64, Ordering::Relaxed);
+        self.0.bytes.fetch_add(ripped.bytes, Ordering::Relaxed);
+
+        if error.is_some() {
+            self.0.errors.fetch_add(1, Ordering::Relaxed);
+        }
+
+        // The feed is only cosmetic, so don't make workers wait for it.
+        if let Some(mut recent) = self.0.recent.try_lock() {
+            recent.push(Activity::new(module, ripped, error));
+        }
+    }
+
+    /// Take the modules processed since the last call
+    pub fn take_recent(&self) -> Feed {
+        std::mem::take(&mut *self.0.recent.lock())
+    }
+}
+
+/// Measures the throughput of a job with an exponential moving average,
+/// so that the estimate doesn't jump around when a few modules take longer.
+#[derive(Debug)]
+pub struct Meter {
+    counters: Counters,
+    stats: Stats,
+    start: Instant,
+    last: Instant,
+    last_processed: u64,
+    last_samples: u64,
+    measured: bool,
+    /// When the job was paused, if it's paused
+    paused_since: Option<Instant>,
+    /// Time spent paused, not counting the current pause
+    paused: Duration,
+}
+
+impl Meter {
+    pub fn new(counters: Counters) -> Self {
+        let now = Instant::now();
+
+        Self {
+            counters,
+            stats: Stats::default(),
+            start: now,
+            last: now,
+            last_processed: 0,
+            last_samples: 0,
+            measured: false,
+            paused_since: None,
+            paused: Duration::ZERO,
+        }
+    }
+
+    /// Time spent paused isn't counted towards the elapsed time or the rates.
+    pub fn set_paused(&mut self, paused: bool) {
+        let now = Instant::now();
+
+        match (paused, self.paused_since) {
+            (true, None) => self.paused_since = Some(now),
+            (false, Some(since)) => {
+                let pause = now - since;
+                self.paused += pause;
+                // Start the next measurement from where it left off
+                self.last += pause;
+                self.paused_since = None;
+            }
+            _ => (),
+        }
+    }
+
+    /// Take the modules processed since the last call
+    pub fn take_recent(&self) -> Feed {
+        self.counters.take_recent()
+    }
+
+    pub fn stats(&mut self) -> Stats {
+        let counters = &self.counters.0;
+
+        self.stats.processed = counters.processed.load(Ordering::Relaxed);
+        self.stats.total = counters.total.load(Ordering::Relaxed);
+        self.stats.errors = counters.errors.load(Ordering::Relaxed);
+        self.stats.samples = counters.samples.load(Ordering::Relaxed);
+        self.stats.skipped = counters.skipped.load(Ordering::Relaxed);
+        self.stats.bytes = counters.bytes.load(Ordering::Relaxed);
+
+        let now = Instant::now();
+        let delta = now - self.last;
+
+        if delta >= INTERVAL && self.paused_since.is_none() {
+            let seconds = delta.as_secs_f32();
+            // The processed count is reset when a new stage starts
+            let processed = self.stats.processed.saturating_sub(self.last_processed);
+            let samples = self.stats.samples - self.last_samples;
+
+            let modules_per_sec = processed as f32 / seconds;
+            let samples_per_sec = samples as f32 / seconds;
+
+            match self.measured {
+                true => {
+                    self.stats.modules_per_sec =
+                        average(self.stats.modules_per_sec, modules_per_sec);
+                    self.stats.samples_per_sec =
+                        average(self.stats.samples_per_sec, samples_per_sec);
+                }
+                false => {
+                    self.stats.modules_per_sec = modules_per_sec;
+                    self.stats.samples_per_sec = samples_per_sec;
+                    self.measured = true;
+                }
+            }
+
+            self.last = now;
+            self.last_processed = self.stats.processed;
+            self.last_samples = self.stats.samples;
+        }
+
+        let remaining = self.stats.total.saturating_sub(self.stats.processed);
+
+        let pausing = self
+            .paused_since
+            .map_or(Duration::ZERO, |since| now - since);
+        self.stats.elapsed = (now - self.start).saturating_sub(self.paused + pausing);
+        self.stats.eta = (self.stats.modules_per_sec > 0.0)
+            .then(|| Duration::from_secs_f32(remaining as f32 / self.stats.modules_per_sec));
+
+        self.stats
+    }
+}
+
+fn average(previous: f32, current: f32) -> f32 {
+    SMOOTHING * current + (1.0 - SMOOTHING) * previous
+}
+
+/// ```text
+/// 1h 02m 03s
+/// 2m 03s
+/// 3s
+/// ```
+pub fn hms(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, (seconds / 60) % 60, seconds % 60);
+
+    match (hours, minutes) {
+        (0, 0) => format!("{}s", seconds),
+        (0, _) => format!("{}m {:02}s", minutes, seconds),
+        _ => format!("{}h {:02}m {:02}s", hours, minutes, seconds),
+    }
+}
@@ -0,0 +1,153 @@
+//! Jobs waiting to be ripped.
+//!
+//! Each job is a snapshot of the entries and configuration at the time it was queued,
+//! so the user is free to prepare the next job while one is running.
+//! Jobs are ripped one after another.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+
+use data::time::Time;
+
+use super::subscription::CompleteState;
+use super::Signal;
+
+#[derive(Debug)]
+pub struct Job {
+    pub id: usize,
+    pub signal: Signal,
+}
+
+impl Job {
+    pub fn entries(&self) -> &[PathBuf] {
+        &self.signal.entries
+    }
+
+    pub fn destination(&self) -> &Path {
+        &self.signal.ripping.destination
+    }
+}
+
+/// A job that has been ripped
+#[derive(Debug)]
+pub struct Completed {
+    pub id: usize,
+    pub entries: usize,
+    pub destination: PathBuf,
+    pub state: CompleteState,
+    pub time: Time,
+}
+
+/// The job being ripped
+#[derive(Debug)]
+struct Current {
+    id: usize,
+    entries: usize,
+    destination: PathBuf,
+}
+
+#[derive(Debug, Default)]
+pub struct Queue {
+    pending: VecDeque<Job>,
+    current: Option<Current>,
+    completed: Vec<Completed>,
+    next_id: usize,
+}
+
+impl Queue {
+    /// Add a job to the end of the queue, returns its id.
+    pub fn push(&mut self, signal: Signal) -> usize {
+        self.next_id += 1;
+        let id = self.next_id;
+
+        tracing::info!("Queued job #{} ({} entries)", id, signal.entries.len());
+        self.pending.push_back(Job { id, signal });
+        id
+    }
+
+    /// Take the next job to rip.
+    pub fn next(&mut self) -> Option<Signal> {
+        let Job { id, signal } = self.pending.pop_front()?;
+
+        self.current = Some(Current {
+            id,
+            entries: signal.entries.len(),
+            destination: signal.ripping.destination.clone(),
+        });
+
+        Some(signal)
+    }
+
+    /// Record how the current job went.
+    pub fn finish(&mut self, state: &CompleteState, time: &Time) {
+        let Some(Current {
+            id,
+            entries,
+            destination,
+        }) = self.current.take()
+        else {
+            return;
+        };
+
+        self.completed.push(Completed {
+            id,
+            entries,
+            destination,
+            state: state.clone(),
+            time: time.clone(),
+        });
+    }
+
+    pub fn remove(&mut self, id: usize) -> Option<Job> {
+        let index = self.position(id)?;
+        self.pending.remove(index)
+    }
+
+    pub fn move_up(&mut self, id: usize) {
+        if let Some(index) = self.position(id).filter(|index| *index > 0) {
+            self.pending.swap(index, index - 1);
+        }
+    }
+
+    pub fn move_down(&mut self, id: usize) {
+        if let Some(index) = self
+            .position(id)
+            .filter(|index| index + 1 < self.pending.len())
+        {
+            self.pending.swap(index, index + 1);
+        }
+    }
+
+    pub fn clear_completed(&mut self) {
+        self.completed.clear();
+    }
+
+    pub fn pending(&self) -> impl Iterator<Item = &Job> {
+        self.pending.iter()
+    }
+
+    pub fn completed(&self) -> &[Completed] {
+        &self.completed
+    }
+
+    pub fn get_completed(&self, id: usize) -> Option<&Completed> {
+        self.completed.iter().find(|job| job.id == id)
+    }
+
+    /// Id of the job being ripped
+    pub fn current(&self) -> Option<usize> {
+        self.current.as_ref().map(|current| current.id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    fn position(&self, id: usize) -> Option<usize> {
+        self.pending.iter().position(|job| job.id == id)
+    }
+}
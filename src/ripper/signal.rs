@@ -1,6 +1,9 @@
 use data::config;
+use data::config::general::ErrorLogFormat;
 use std::path::PathBuf;
 
+use super::stop_flag::StopToken;
+
 /// Constructed and sent by the main GUI
 /// to the subscription
 #[derive(Debug)]
@@ -8,6 +11,10 @@ pub struct Signal {
     pub entries: Vec<PathBuf>,
     pub ripping: config::SampleRippingConfig,
     pub naming: config::SampleNameConfig,
+    pub error_log_format: ErrorLogFormat,
+    pub logging_path: Option<PathBuf>,
+    /// Stops this job, and only this job
+    pub stop: StopToken,
 }
 
 impl Signal {
@@ -15,11 +22,16 @@ impl Signal {
         entries: Vec<PathBuf>,
         ripping: config::SampleRippingConfig,
         naming: config::SampleNameConfig,
+        error_log_format: ErrorLogFormat,
+        logging_path: Option<PathBuf>,
     ) -> Self {
         Self {
             ripping,
             naming,
             entries,
+            error_log_format,
+            logging_path,
+            stop: StopToken::new(),
         }
     }
 }
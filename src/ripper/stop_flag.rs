@@ -1,60 +1,126 @@
-//! When we need to stop the ripping process for some reason.
+//! When we need to stop a ripping job for some reason.
 //!
 //! Uses atomics internally since the extraction uses threads to parallelize
 //! the process.
 //!
+//! Every job owns a [StopToken], which is passed to the extraction through the [Signal](super::Signal).
+//! This way, independent jobs can be stopped without affecting each other.
+//!
 //! Additionally, if one of the threads panic for whatever reason, the crash handler will kick in.
 //!
 //! The crash handler MUST be able to abort the ripping process, otherwise we could have multiple panics
-//! (and multiple error boxes).
-
-use std::sync::atomic::{AtomicU8, Ordering};
+//! (and multiple error boxes). Threads working on a job register its token, so that the crash handler
+//! only aborts the job whose thread panicked.
+//!
+//! Pausing is tracked separately, workers finish the module they're ripping before they wait.
 
-static STOP_FLAG: AtomicU8 = AtomicU8::new(0);
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 const NONE: u8 = 0;
 const CANCEL: u8 = 1;
 const ABORT: u8 = 2;
 
-/// Has the stopped flag been set?
-pub fn is_set() -> bool {
-    STOP_FLAG.load(Ordering::Acquire) != NONE
+thread_local! {
+    /// The job the current thread is working on
+    static CURRENT: RefCell<Option<StopToken>> = const { RefCell::new(None) };
 }
 
-/// Has the cancelled flag been set?
-pub fn is_cancelled() -> bool {
-    STOP_FLAG.load(Ordering::Acquire) == CANCEL
+#[derive(Debug, Default)]
+struct Flags {
+    stop: AtomicU8,
+    paused: AtomicBool,
 }
 
-/// Has the aborted flag been set?
-pub fn is_aborted() -> bool {
-    STOP_FLAG.load(Ordering::Acquire) == ABORT
-}
+/// Cancellation state of a single job, cheap to clone.
+#[derive(Debug, Clone, Default)]
+pub struct StopToken(Arc<Flags>);
+
+impl StopToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Has the stopped flag been set?
+    pub fn is_set(&self) -> bool {
+        self.0.stop.load(Ordering::Acquire) != NONE
+    }
+
+    /// Has the cancelled flag been set?
+    pub fn is_cancelled(&self) -> bool {
+        self.0.stop.load(Ordering::Acquire) == CANCEL
+    }
+
+    /// Has the aborted flag been set?
+    pub fn is_aborted(&self) -> bool {
+        self.0.stop.load(Ordering::Acquire) == ABORT
+    }
+
+    /// Set flag to cancel (only if abort flag isn't set)
+    pub fn set_cancel(&self) {
+        let _ = self
+            .0
+            .stop
+            .compare_exchange(NONE, CANCEL, Ordering::AcqRel, Ordering::Acquire);
+    }
 
-/// Reset the flag back to its original state (only if abort flag isn't set)
-pub fn reset() {
-    if !is_aborted() {
-        STOP_FLAG.store(NONE, Ordering::Relaxed);
+    pub fn get_flag(&self) -> StopFlag {
+        match self.0.stop.load(Ordering::Acquire) {
+            NONE => StopFlag::None,
+            CANCEL => StopFlag::Cancel,
+            ABORT => StopFlag::Abort,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Has the job been paused?
+    pub fn is_paused(&self) -> bool {
+        self.0.paused.load(Ordering::Acquire)
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.0.paused.store(paused, Ordering::Release);
+    }
+
+    /// Block the calling thread while the job is paused.
+    ///
+    /// Returns early if the stop flag is set, so a paused job can still be cancelled.
+    pub fn wait_while_paused(&self) {
+        while self.is_paused() && !self.is_set() {
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    /// Mark the calling thread as working on this job.
+    pub fn register(&self) {
+        CURRENT.with(|current| *current.borrow_mut() = Some(self.clone()));
     }
 }
 
+/// Abort the job the calling thread is working on, if any.
+///
 /// TODO: This should only be called by the panic handler.
-/// But I have no idea how to enforce this... 
-/// 
+/// But I have no idea how to enforce this...
+///
 /// `pub(in crate::logger::crash_handler)` doesn't work
-/// 
+///
 /// For now, we'll just use `track_caller` to keep a close eye on it...
 #[track_caller]
-pub(in crate) fn set_abort() {
-    STOP_FLAG.store(ABORT, Ordering::Release);
-    tracing::warn!("ABORT triggered from: {}", std::panic::Location::caller());
-}
+pub(crate) fn abort_current() {
+    let location = std::panic::Location::caller();
 
-/// Set flag to cancel (only if abort flag isn't set)
-pub fn set_cancel() {
-    if !is_aborted() {
-        STOP_FLAG.store(CANCEL, Ordering::Release);
-    }
+    let _ = CURRENT.try_with(|current| {
+        let Ok(current) = current.try_borrow() else {
+            return;
+        };
+
+        if let Some(token) = current.as_ref() {
+            tracing::warn!("ABORT triggered from: {}", location);
+            token.0.stop.store(ABORT, Ordering::Release);
+        }
+    });
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -65,12 +131,3 @@ pub enum StopFlag {
     Cancel = CANCEL,
     Abort = ABORT,
 }
-
-pub fn get_flag() -> StopFlag {
-    match STOP_FLAG.load(Ordering::Acquire) {
-        NONE => StopFlag::None,
-        CANCEL => StopFlag::Cancel,
-        ABORT => StopFlag::Abort,
-        _ => unreachable!(),
-    }
-}
@@ -1,21 +1,29 @@
 use data::time::Time;
 
 use iced::{futures::SinkExt, subscription, Subscription};
-use std::{any::TypeId, path::PathBuf};
+use std::{any::TypeId, path::PathBuf, time::Duration};
 use tokio::sync::mpsc::{self, Receiver, Sender, UnboundedReceiver};
+use tokio::time::Instant;
 use tracing::{error, info};
 
+use super::activity::Feed;
+use super::extraction::error_handler;
 pub use super::extraction::{self, ErrorHandler, Failed, Message as ThreadMessage, StopMessage};
-use super::stop_flag::{self, StopFlag};
+use super::progress::{Counters, Meter, Stats};
+use super::stop_flag::{StopFlag, StopToken};
 use super::Signal;
 
+/// How often the progress of a job is sent to the application
+const SAMPLE_RATE: Duration = Duration::from_millis(100);
+
 /// Messages emitted by subscription
 #[derive(Clone, Debug)]
 pub enum Message {
     Ready(Sender<Signal>),
     Progress {
-        progress: f32,
-        errors: u64,
+        stats: Stats,
+        /// Modules processed since the last update
+        recent: Feed,
     },
     Done {
         state: CompleteState,
@@ -77,7 +85,7 @@ impl From<ErrorHandler> for CompleteState {
 ///
 /// * It has been (re)initialized. This is so that the app can send the files/folders to rip and the configuration.
 /// * The worker sends custom messages to keep the user updated. E.g ``"Traversing folders..."``, ``"Ripping 100 files..."``
-/// * A module has/can't be ripped. This is also done to track progress.
+/// * Every [SAMPLE_RATE] while ripping, to keep track of progress.
 /// * The worker has finished ripping.
 pub fn subscription() -> Subscription<Message> {
     struct Ripper;
@@ -88,10 +96,10 @@ pub fn subscription() -> Subscription<Message> {
             Idle(Receiver<Signal>),
             Ripping {
                 ripping_msg: UnboundedReceiver<ThreadMessage>,
-                total: u64,
-                progress: u64,
+                meter: Meter,
+                next_sample: Instant,
                 error_handler: ErrorHandler,
-                total_errors: u64,
+                stop: StopToken,
                 timer: Time,
                 destination: PathBuf,
             },
@@ -102,8 +110,6 @@ pub fn subscription() -> Subscription<Message> {
         loop {
             match &mut state {
                 State::Init => {
-                    stop_flag::reset();
-
                     let (sender, receiver) = mpsc::channel::<Signal>(1);
                     state = State::Idle(receiver);
 
@@ -115,23 +121,30 @@ pub fn subscription() -> Subscription<Message> {
                 }
                 State::Idle(start_msg) => {
                     if let Some(config) = start_msg.recv().await {
-                        let total = config.entries.len() as u64;
+                        let counters = Counters::new();
+                        counters.set_total(config.entries.len() as u64);
+                        let meter = Meter::new(counters.clone());
+
                         let destination = config.ripping.destination.clone();
+                        let log_dir =
+                            error_handler::log_dir(config.logging_path.as_deref(), &destination);
+                        let error_handler = ErrorHandler::new(log_dir, config.error_log_format);
+                        let stop = config.stop.clone();
                         let (tx, rx) = mpsc::unbounded_channel();
 
                         // The ripping process is delegated by the subscription to a separate thread.
                         // This might not be idiomatic, but it works...
                         std::thread::spawn(move || {
                             info!("Started ripping");
-                            extraction::rip(tx, config);
+                            extraction::rip(tx, config, counters);
                         });
 
                         state = State::Ripping {
                             ripping_msg: rx,
-                            total,
-                            progress: 0,
-                            error_handler: ErrorHandler::new(destination.clone()),
-                            total_errors: 0,
+                            meter,
+                            next_sample: Instant::now() + SAMPLE_RATE,
+                            error_handler,
+                            stop,
                             timer: Time::init(),
                             destination,
                         };
@@ -139,104 +152,109 @@ pub fn subscription() -> Subscription<Message> {
                 }
                 State::Ripping {
                     ripping_msg,
-                    total_errors,
+                    meter,
+                    next_sample,
                     error_handler,
-                    progress,
-                    total,
+                    stop,
                     timer,
                     destination,
-                } => match ripping_msg.recv().await {
-                    Some(ThreadMessage::Progress(error)) => {
-                        *progress += 1;
-                        let percentage: f32 = (*progress as f32 / *total as f32) * 100.0;
+                } => {
+                    let message = tokio::time::timeout_at(*next_sample, ripping_msg.recv()).await;
 
-                        if let Some(failed) = error {
-                            error!("{}", &failed);
-                            *total_errors += 1;
-                            error_handler.push(failed).await;
-                        }
+                    // Check the deadline even if a message arrived in time,
+                    // otherwise a steady stream of failures would stall the progress.
+                    if Instant::now() >= *next_sample {
+                        *next_sample = Instant::now() + SAMPLE_RATE;
+                        meter.set_paused(stop.is_paused());
+                        timer.set_paused(stop.is_paused());
 
                         let _ = output.try_send(Message::Progress {
-                            progress: percentage,
-                            errors: *total_errors,
+                            stats: meter.stats(),
+                            recent: meter.take_recent(),
                         });
                     }
-                    Some(ThreadMessage::SetTotal(new_total)) => {
-                        *total = new_total;
-                        *progress = 0;
-                    }
-                    Some(ThreadMessage::Info(info)) => {
-                        let _ = output.try_send(Message::Info(info));
-                    }
-                    Some(ThreadMessage::Stop(stop)) => {
-                        timer.stop();
 
-                        let completed_state = match stop {
-                            StopMessage::Abort => CompleteState::Aborted,
-                            StopMessage::Cancel => CompleteState::Cancelled,
-                        };
+                    let Ok(message) = message else {
+                        continue;
+                    };
 
-                        let msg = Message::Done {
-                            state: completed_state,
-                            time: std::mem::take(timer),
-                            destination: std::mem::take(destination),
-                        };
+                    match message {
+                        Some(ThreadMessage::Failed(failed)) => {
+                            error!("{}", &failed);
+                            error_handler.push(failed).await;
+                        }
+                        Some(ThreadMessage::Info(info)) => {
+                            let _ = output.try_send(Message::Info(info));
+                        }
+                        Some(ThreadMessage::Stop(stop)) => {
+                            timer.stop();
 
-                        info!("Cancelled!");
-                        output
-                            .send(msg)
-                            .await
-                            .expect("Sending 'extraction complete' message to application.");
+                            let completed_state = match stop {
+                                StopMessage::Abort => CompleteState::Aborted,
+                                StopMessage::Cancel => CompleteState::Cancelled,
+                            };
 
-                        state = State::Init;
-                    }
-                    Some(ThreadMessage::Done) => {
-                        timer.stop();
-                        let error = std::mem::take(error_handler);
-
-                        let msg = Message::Done {
-                            state: CompleteState::from(error),
-                            time: std::mem::take(timer),
-                            destination: std::mem::take(destination),
-                        };
+                            let msg = Message::Done {
+                                state: completed_state,
+                                time: std::mem::take(timer),
+                                destination: std::mem::take(destination),
+                            };
 
-                        // It's important that this gets delivered, otherwise the program would be in an invalid state.
-                        output
-                            .send(msg)
-                            .await
-                            .expect("Sending 'extraction complete' message to application.");
+                            info!("Cancelled!");
+                            output
+                                .send(msg)
+                                .await
+                                .expect("Sending 'extraction complete' message to application.");
 
-                        info!("Done!");
-                        state = State::Init;
-                    }
-                    None => {
-                        timer.stop();
-                        let error = std::mem::take(error_handler);
-
-                        let completed_state: CompleteState = match stop_flag::get_flag() {
-                            StopFlag::None => CompleteState::from(error),
-                            StopFlag::Cancel => CompleteState::Cancelled,
-                            StopFlag::Abort => CompleteState::Aborted,
-                        };
+                            state = State::Init;
+                        }
+                        Some(ThreadMessage::Done) => {
+                            timer.stop();
+                            let error = std::mem::take(error_handler);
 
-                        let msg = Message::Done {
-                            state: completed_state,
-                            time: std::mem::take(timer),
-                            destination: std::mem::take(destination),
-                        };
+                            let msg = Message::Done {
+                                state: CompleteState::from(error),
+                                time: std::mem::take(timer),
+                                destination: std::mem::take(destination),
+                            };
 
-                        
-                        tracing::error!("Lost communication with the workers. This usually means something bad happened...");
+                            // It's important that this gets delivered, otherwise the program would be in an invalid state.
+                            output
+                                .send(msg)
+                                .await
+                                .expect("Sending 'extraction complete' message to application.");
 
-                        // It's important that this gets delivered, otherwise the program would be in an invalid state.
-                        output
-                            .send(msg)
-                            .await
-                            .expect("Sending 'extraction complete' message to application.");
+                            info!("Done!");
+                            state = State::Init;
+                        }
+                        None => {
+                            timer.stop();
+                            let error = std::mem::take(error_handler);
 
-                        state = State::Init;
+                            let completed_state: CompleteState = match stop.get_flag() {
+                                StopFlag::None => CompleteState::from(error),
+                                StopFlag::Cancel => CompleteState::Cancelled,
+                                StopFlag::Abort => CompleteState::Aborted,
+                            };
+
+                            let msg = Message::Done {
+                                state: completed_state,
+                                time: std::mem::take(timer),
+                                destination: std::mem::take(destination),
+                            };
+
+                            tracing::error!("Lost communication with the workers. This usually means something bad happened...");
+
+                            // It's important that this gets delivered, otherwise the program would be in an invalid state.
+                            output
+                                .send(msg)
+                                .await
+                                .expect("Sending 'extraction complete' message to application.");
+
+                            state = State::Init;
+                        }
                     }
-                },
+                }
             }
         }
     })
@@ -5,6 +5,9 @@ pub mod build_info;
 pub mod config;
 pub mod crash;
 pub mod entry;
+pub mod error_browser;
+pub mod profiles;
+pub mod queue;
 pub mod ripping;
 pub mod sample_player;
 pub mod settings;
@@ -1,5 +1,7 @@
 //! Configure the behaviour of XMODITS' ripping routine
 
 pub mod name_preview;
+pub mod sample_conversion;
 pub mod sample_naming;
+pub mod sample_processing;
 pub mod sample_ripping;
@@ -1,8 +1,11 @@
 //! Preview how ripped samples will be named
 
+use data::config::sample_ripping::Channels;
 use data::config::{SampleNameConfig, SampleNameParams, SampleRippingConfig};
 use xmodits_lib::interface::{name::Context, Sample};
 
+use crate::exporter::Side;
+
 pub fn preview_name<'a>(
     params: &SampleNameParams,
     naming: &'a SampleNameConfig,
@@ -13,7 +16,6 @@ pub fn preview_name<'a>(
     let source_path = &params.module_source;
 
     let namer_func = naming.build_func();
-    let formatter = ripping.exported_format.get_impl();
 
     let dummy_sample = Sample {
         filename: filename.map(|f| f.into_boxed_str()),
@@ -24,10 +26,21 @@ pub fn preview_name<'a>(
 
     let context = Context {
         total: 10,
-        extension: formatter.extension(),
+        extension: ripping.exported_format.extension(),
         highest: 10,
         source_path: Some(source_path),
     };
 
-    namer_func(&dummy_sample, &context, params.seq_index as usize)
+    let name = namer_func(&dummy_sample, &context, params.seq_index as usize);
+
+    // Stereo samples are written to separate files
+    match ripping.channels {
+        Channels::Split => format!(
+            "{}\nStereo: {}, {}",
+            name,
+            Side::Left.rename(&name),
+            Side::Right.rename(&name)
+        ),
+        _ => name,
+    }
 }
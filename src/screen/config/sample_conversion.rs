@@ -0,0 +1,70 @@
+//! Configure the sample rate and bit depth of exported samples
+
+use data::config::sample_conversion::{BitDepth, Resampler, SampleConversionConfig};
+
+use crate::widget::helpers::{control, labelled_picklist};
+use crate::widget::Element;
+
+use iced::widget::{checkbox, column, horizontal_rule};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Rate(Rate),
+    BitDepth(BitDepth),
+    Resampler(Resampler),
+    Dither(bool),
+}
+
+pub fn update(cfg: &mut SampleConversionConfig, message: Message) {
+    tracing::info!("{:?}", &message);
+
+    match message {
+        Message::Rate(Rate(rate)) => cfg.rate = rate,
+        Message::BitDepth(depth) => cfg.bit_depth = depth,
+        Message::Resampler(resampler) => cfg.resampler = resampler,
+        Message::Dither(dither) => cfg.dither = dither,
+    }
+}
+
+pub fn view(conversion: &SampleConversionConfig) -> Element<Message> {
+    let rate = labelled_picklist(
+        "Sample Rate",
+        [0, 22050, 32000, 44100, 48000, 96000].map(Rate).to_vec(),
+        Some(Rate(conversion.rate)),
+        Message::Rate,
+    );
+
+    let resampler = labelled_picklist(
+        "Resampler",
+        Resampler::ALL,
+        Some(conversion.resampler),
+        Message::Resampler,
+    );
+
+    let bit_depth = labelled_picklist(
+        "Bit Depth",
+        BitDepth::ALL,
+        Some(conversion.bit_depth),
+        Message::BitDepth,
+    );
+
+    let dither =
+        checkbox("Dither When Reducing Bit Depth", conversion.dither).on_toggle(Message::Dither);
+
+    let settings = column![rate, resampler, horizontal_rule(1), bit_depth, dither].spacing(8);
+
+    control("Format Conversion", settings).into()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Rate(pub u32);
+
+impl std::fmt::Display for Rate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => write!(f, "Original"),
+            n => write!(f, "{} Hz", n),
+        }
+    }
+}
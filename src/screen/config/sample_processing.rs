@@ -0,0 +1,116 @@
+//! Configure how samples should be processed before they're exported
+
+use data::config::sample_processing::{Normalize, SampleProcessingConfig};
+
+use crate::widget::helpers::{control, labelled_picklist};
+use crate::widget::Element;
+
+use iced::widget::{checkbox, column, horizontal_rule};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    RemoveDCOffset(bool),
+    TrimSilence(bool),
+    SilenceThreshold(Decibels),
+    Normalize(Normalize),
+    NormalizeTarget(Decibels),
+    FadeIn(Milliseconds),
+    FadeOut(Milliseconds),
+}
+
+pub fn update(cfg: &mut SampleProcessingConfig, message: Message) {
+    tracing::info!("{:?}", &message);
+
+    match message {
+        Message::RemoveDCOffset(toggle) => cfg.remove_dc_offset = toggle,
+        Message::TrimSilence(toggle) => cfg.trim_silence = toggle,
+        Message::SilenceThreshold(Decibels(db)) => cfg.silence_threshold = db,
+        Message::Normalize(normalize) => cfg.normalize = normalize,
+        Message::NormalizeTarget(Decibels(db)) => cfg.normalize_target = db,
+        Message::FadeIn(Milliseconds(ms)) => cfg.fade_in = ms,
+        Message::FadeOut(Milliseconds(ms)) => cfg.fade_out = ms,
+    }
+}
+
+pub fn view(processing: &SampleProcessingConfig) -> Element<Message> {
+    let checkboxes = column![
+        checkbox("Remove DC Offset", processing.remove_dc_offset)
+            .on_toggle(Message::RemoveDCOffset),
+        checkbox("Trim Silence", processing.trim_silence).on_toggle(Message::TrimSilence),
+    ]
+    .spacing(8);
+
+    let silence_threshold = labelled_picklist(
+        "Silence Threshold",
+        [-40, -50, -60, -70, -80].map(Decibels).to_vec(),
+        Some(Decibels(processing.silence_threshold)),
+        Message::SilenceThreshold,
+    );
+
+    let normalize = labelled_picklist(
+        "Normalize",
+        Normalize::ALL,
+        Some(processing.normalize),
+        Message::Normalize,
+    );
+
+    let normalize_target = labelled_picklist(
+        "Normalize Target",
+        [0, -1, -3, -6, -12, -18].map(Decibels).to_vec(),
+        Some(Decibels(processing.normalize_target)),
+        Message::NormalizeTarget,
+    );
+
+    let fades = [0, 1, 2, 5, 10, 20].map(Milliseconds).to_vec();
+
+    let fade_in = labelled_picklist(
+        "Fade In",
+        fades.clone(),
+        Some(Milliseconds(processing.fade_in)),
+        Message::FadeIn,
+    );
+
+    let fade_out = labelled_picklist(
+        "Fade Out",
+        fades,
+        Some(Milliseconds(processing.fade_out)),
+        Message::FadeOut,
+    );
+
+    let settings = column![
+        checkboxes,
+        silence_threshold,
+        horizontal_rule(1),
+        normalize,
+        normalize_target,
+        horizontal_rule(1),
+        fade_in,
+        fade_out,
+    ]
+    .spacing(8);
+
+    control("Sample Processing", settings).into()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Decibels(pub i8);
+
+impl std::fmt::Display for Decibels {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} dB", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Milliseconds(pub u16);
+
+impl std::fmt::Display for Milliseconds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => write!(f, "Off"),
+            n => write!(f, "{} ms", n),
+        }
+    }
+}
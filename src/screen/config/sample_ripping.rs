@@ -2,15 +2,17 @@
 
 use std::path::{Path, PathBuf};
 
+use data::config::sample_ripping::{Channels, ExportFormat, Sidecar, StaticNoise};
 use data::config::SampleRippingConfig;
-use xmodits_lib::exporter::AudioFormat;
 
+use crate::screen::config::sample_processing::Decibels;
 use crate::theme;
 use crate::utils::folder_dialog;
 use crate::widget::helpers::{centered_button, control, labelled_picklist};
 use crate::widget::Element;
 
-use iced::widget::{checkbox, column, horizontal_rule, row, text_input};
+use iced::widget::tooltip::Position;
+use iced::widget::{checkbox, column, horizontal_rule, row, text_input, tooltip};
 use iced::{Command, Length};
 
 use once_cell::sync::Lazy;
@@ -24,7 +26,19 @@ pub fn destination_is_valid(ripping: &SampleRippingConfig) -> bool {
 
 #[derive(Debug, Clone)]
 pub enum Message {
-    ExportFormat(AudioFormat),
+    ExportFormat(ExportFormat),
+    Channels(Channels),
+    EmbedLoopPoints(bool),
+    EmbedTags(bool),
+    GenerateSfz(bool),
+    GenerateSf2(bool),
+    Sf2SamplePresets(bool),
+    Sidecar(Sidecar),
+    StaticNoise(StaticNoise),
+    MinFrames(Frames),
+    SkipSilent(bool),
+    SilenceThreshold(Decibels),
+    SkipDuplicates(bool),
     SelfContained(bool),
     StrictLoad(bool),
     WorkerThreads(Workers),
@@ -38,6 +52,18 @@ pub fn update(cfg: &mut SampleRippingConfig, message: Message) -> Command<Messag
 
     match message {
         Message::ExportFormat(format) => cfg.exported_format = format,
+        Message::Channels(channels) => cfg.channels = channels,
+        Message::EmbedLoopPoints(toggle) => cfg.embed_loop_points = toggle,
+        Message::EmbedTags(toggle) => cfg.embed_tags = toggle,
+        Message::GenerateSfz(toggle) => cfg.generate_sfz = toggle,
+        Message::GenerateSf2(toggle) => cfg.generate_sf2 = toggle,
+        Message::Sf2SamplePresets(toggle) => cfg.sf2_sample_presets = toggle,
+        Message::Sidecar(sidecar) => cfg.sidecar = sidecar,
+        Message::StaticNoise(noise) => cfg.static_noise = noise,
+        Message::MinFrames(Frames(frames)) => cfg.min_frames = frames,
+        Message::SkipSilent(toggle) => cfg.skip_silent = toggle,
+        Message::SilenceThreshold(Decibels(db)) => cfg.silence_threshold = db,
+        Message::SkipDuplicates(toggle) => cfg.skip_duplicates = toggle,
         Message::SelfContained(toggle) => cfg.self_contained = toggle,
         Message::FolderDepth(depth) => cfg.folder_max_depth = depth,
         Message::StrictLoad(strict) => cfg.strict = strict,
@@ -90,6 +116,13 @@ pub fn view(ripping: &SampleRippingConfig) -> Element<Message> {
         Message::ExportFormat,
     );
 
+    let channels = labelled_picklist(
+        "Channels",
+        Channels::ALL,
+        Some(ripping.channels),
+        Message::Channels,
+    );
+
     let folder_scan_depth = labelled_picklist(
         "Folder Scan Depth",
         [1, 2, 3, 4, 5, 6, 7].as_slice(),
@@ -108,6 +141,7 @@ pub fn view(ripping: &SampleRippingConfig) -> Element<Message> {
     let settings = column![
         col1,
         export_format,
+        channels,
         horizontal_rule(1),
         folder_scan_depth,
         worker_threads,
@@ -117,6 +151,97 @@ pub fn view(ripping: &SampleRippingConfig) -> Element<Message> {
     control("Ripping Configuration", settings).into()
 }
 
+/// Information embedded in exported WAV and AIFF files
+pub fn view_metadata(ripping: &SampleRippingConfig) -> Element<Message> {
+    let settings = column![
+        checkbox("Embed Loop Points", ripping.embed_loop_points)
+            .on_toggle(Message::EmbedLoopPoints),
+        checkbox("Embed Tags", ripping.embed_tags).on_toggle(Message::EmbedTags),
+        horizontal_rule(1),
+        labelled_picklist(
+            "JSON Sidecar",
+            Sidecar::ALL,
+            Some(ripping.sidecar),
+            Message::Sidecar
+        ),
+    ]
+    .spacing(8);
+
+    control("Metadata", settings).into()
+}
+
+const SF2_HINT: &str =
+    "xmodits doesn't read the module's instruments, so they can't be SoundFont presets.\n\
+    Samples are laid out in a drum kit preset (bank 128), and can each have their own preset.";
+
+pub fn view_instruments(ripping: &SampleRippingConfig) -> Element<Message> {
+    let settings = column![
+        checkbox("Generate SFZ", ripping.generate_sfz).on_toggle(Message::GenerateSfz),
+        tooltip(
+            checkbox("Generate SoundFont (SF2)", ripping.generate_sf2)
+                .on_toggle(Message::GenerateSf2),
+            SF2_HINT,
+            Position::Bottom,
+        )
+        .padding(6)
+        .style(theme::Container::Frame),
+        checkbox("SF2 Preset per Sample", ripping.sf2_sample_presets)
+            .on_toggle(Message::Sf2SamplePresets),
+    ]
+    .spacing(8);
+
+    control("Instruments", settings).into()
+}
+
+/// Samples that shouldn't be ripped
+pub fn view_filters(ripping: &SampleRippingConfig) -> Element<Message> {
+    let min_frames = labelled_picklist(
+        "Minimum Length",
+        [0, 2, 16, 64, 256, 1024].map(Frames).to_vec(),
+        Some(Frames(ripping.min_frames)),
+        Message::MinFrames,
+    );
+
+    let silence_threshold = labelled_picklist(
+        "Silence Threshold",
+        [-40, -50, -60, -70, -80, -90].map(Decibels).to_vec(),
+        Some(Decibels(ripping.silence_threshold)),
+        Message::SilenceThreshold,
+    );
+
+    let static_noise = labelled_picklist(
+        "Static Noise",
+        StaticNoise::ALL,
+        Some(ripping.static_noise),
+        Message::StaticNoise,
+    );
+
+    let settings = column![
+        min_frames,
+        checkbox("Skip Silent Samples", ripping.skip_silent).on_toggle(Message::SkipSilent),
+        silence_threshold,
+        checkbox("Skip Duplicates", ripping.skip_duplicates).on_toggle(Message::SkipDuplicates),
+        horizontal_rule(1),
+        static_noise,
+    ]
+    .spacing(8);
+
+    control("Filters", settings).into()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct Frames(pub u32);
+
+impl std::fmt::Display for Frames {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            0 => write!(f, "Off"),
+            n => write!(f, "{} frames", n),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Eq)]
 #[repr(transparent)]
 pub struct Workers(pub usize);
@@ -0,0 +1,284 @@
+//! Browse the errors of a finished job
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+use iced::widget::{button, checkbox, column, container, row, scrollable, text, text_input, Space};
+use iced::{Alignment, Command, Length};
+
+use crate::ripper::extraction::error::{Failed, Reason};
+use crate::ripper::extraction::ErrorHandler;
+use crate::ripper::subscription::CompleteState;
+use crate::widget::helpers::{centered_container, fill_container, text_adv};
+use crate::widget::Element;
+use crate::{icon, theme};
+
+const PAGE_SIZE: usize = 50;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Loaded(Result<Vec<Failed>, String>),
+    Filter(String),
+    Group(bool),
+    Page(usize),
+    Expand(usize),
+    /// Handled by the application
+    Preview(PathBuf),
+    CopyPath(PathBuf),
+    Close,
+}
+
+#[derive(Debug, Default)]
+enum State {
+    #[default]
+    Closed,
+    Loading,
+    Failed(String),
+    Loaded,
+}
+
+#[derive(Debug, Default)]
+pub struct ErrorBrowser {
+    state: State,
+    errors: Vec<Failed>,
+    filter: String,
+    group: bool,
+    page: usize,
+    /// Indices of the errors that show their samples
+    expanded: HashSet<usize>,
+}
+
+impl ErrorBrowser {
+    /// Browse the errors, they're read back from the log file if they were spilled to one.
+    pub fn open(state: &CompleteState) -> (Self, Command<Message>) {
+        let mut browser = Self::default();
+
+        let command = match state {
+            CompleteState::TooMuchErrors { log, .. } => {
+                browser.state = State::Loading;
+                Command::perform(ErrorHandler::read(log.to_owned()), Message::Loaded)
+            }
+            state => {
+                browser.state = State::Loaded;
+                browser.errors = state.errors_ref().cloned().unwrap_or_default();
+                Command::none()
+            }
+        };
+
+        (browser, command)
+    }
+
+    pub fn is_open(&self) -> bool {
+        !matches!(self.state, State::Closed)
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        match message {
+            Message::Loaded(Ok(errors)) => {
+                self.errors = errors;
+                self.state = State::Loaded;
+            }
+            Message::Loaded(Err(reason)) => self.state = State::Failed(reason),
+            Message::Filter(filter) => {
+                self.filter = filter;
+                self.page = 0;
+            }
+            Message::Group(group) => {
+                self.group = group;
+                self.page = 0;
+            }
+            Message::Page(page) => self.page = page,
+            Message::Expand(index) => {
+                if !self.expanded.remove(&index) {
+                    self.expanded.insert(index);
+                }
+            }
+            Message::CopyPath(path) => return iced::clipboard::write(path.display().to_string()),
+            Message::Close => *self = Self::default(),
+            Message::Preview(_) => (),
+        }
+        Command::none()
+    }
+
+    /// Indices of the errors that match the filter, grouped by their reason if enabled.
+    fn visible(&self) -> Vec<usize> {
+        let filter = self.filter.to_lowercase();
+
+        let mut visible: Vec<usize> = self
+            .errors
+            .iter()
+            .enumerate()
+            .filter(|(_, error)| filter.is_empty() || matches(error, &filter))
+            .map(|(index, _)| index)
+            .collect();
+
+        if self.group {
+            visible.sort_by_cached_key(|index| group(&self.errors[*index]));
+        }
+
+        visible
+    }
+
+    pub fn view(&self) -> Element<Message> {
+        let content: Element<Message> = match &self.state {
+            State::Closed => Space::new(0, 0).into(),
+            State::Loading => centered_container(text("Reading the error log...")).into(),
+            State::Failed(reason) => {
+                centered_container(text(format!("Couldn't read the error log: {}", reason))).into()
+            }
+            State::Loaded => self.view_errors(),
+        };
+
+        let back = button("Back")
+            .on_press(Message::Close)
+            .style(theme::Button::Cancel)
+            .padding(5);
+
+        let search = text_input("Filter by path or reason...", &self.filter)
+            .on_input(Message::Filter)
+            .padding(5);
+
+        let top = row![
+            back,
+            search,
+            checkbox("Group by Reason", self.group).on_toggle(Message::Group)
+        ]
+        .spacing(8)
+        .align_items(Alignment::Center);
+
+        fill_container(column![top, content].spacing(8))
+            .padding(8)
+            .style(theme::Container::Black)
+            .into()
+    }
+
+    fn view_errors(&self) -> Element<Message> {
+        let visible = self.visible();
+        let pages = visible.len().div_ceil(PAGE_SIZE).max(1);
+        let page = self.page.min(pages - 1);
+
+        let mut rows = column![].spacing(8);
+        let mut previous_group: Option<String> = None;
+
+        for index in visible.iter().skip(page * PAGE_SIZE).take(PAGE_SIZE) {
+            let error = &self.errors[*index];
+
+            if self.group {
+                let key = group(error);
+
+                if previous_group.as_ref() != Some(&key) {
+                    let total = visible
+                        .iter()
+                        .filter(|i| group(&self.errors[**i]) == key)
+                        .count();
+
+                    let header = text(format!("{} ({})", key, total)).style(theme::Text::Warning);
+                    rows = rows.push(header);
+                    previous_group = Some(key);
+                }
+            }
+
+            rows = rows.push(self.view_error(*index, error));
+        }
+
+        let previous = button("<").on_press_maybe((page > 0).then(|| Message::Page(page - 1)));
+        let next = button(">").on_press_maybe((page + 1 < pages).then(|| Message::Page(page + 1)));
+
+        let pagination = row![
+            previous,
+            text(format!(
+                "Page {} of {} - {} of {} errors",
+                page + 1,
+                pages,
+                visible.len(),
+                self.errors.len()
+            )),
+            next
+        ]
+        .spacing(8)
+        .align_items(Alignment::Center);
+
+        column![
+            fill_container(scrollable(row![rows, Space::with_width(15)])),
+            pagination
+        ]
+        .spacing(8)
+        .align_items(Alignment::Center)
+        .into()
+    }
+
+    fn view_error<'a>(&'a self, index: usize, error: &'a Failed) -> Element<'a, Message> {
+        let expanded = self.expanded.contains(&index);
+
+        let expand = match &error.reason {
+            Reason::Single(_) => None,
+            Reason::Multiple(errors) => Some(
+                button(text(match expanded {
+                    true => "Hide Samples".to_owned(),
+                    false => format!("Show {} Samples", errors.len()),
+                }))
+                .on_press(Message::Expand(index))
+                .style(theme::Button::Hyperlink),
+            ),
+        };
+
+        let actions = row![]
+            .push_maybe(expand)
+            .push(Space::with_width(Length::Fill))
+            .push(
+                button(icon::play())
+                    .on_press(Message::Preview(error.path.clone()))
+                    .style(theme::Button::Dark),
+            )
+            .push(
+                button("Copy Path")
+                    .on_press(Message::CopyPath(error.path.clone()))
+                    .style(theme::Button::Dark),
+            )
+            .spacing(5)
+            .align_items(Alignment::Center);
+
+        let samples = match &error.reason {
+            Reason::Multiple(errors) if expanded => Some(
+                column(errors.iter().map(|error| text_adv(error).into()))
+                    .spacing(4)
+                    .padding([0, 0, 0, 15]),
+            ),
+            _ => None,
+        };
+
+        let content = column![
+            text_adv(error.filename()),
+            text_adv(error.path.display()).size(11),
+            text(&error.reason),
+        ]
+        .push_maybe(samples)
+        .push(actions)
+        .spacing(4);
+
+        container(content)
+            .padding(6)
+            .width(Length::Fill)
+            .style(theme::Container::Frame)
+            .into()
+    }
+}
+
+fn matches(error: &Failed, filter: &str) -> bool {
+    let contains = |haystack: &str| haystack.to_lowercase().contains(filter);
+
+    contains(&error.path.display().to_string())
+        || match &error.reason {
+            Reason::Single(reason) => contains(reason),
+            Reason::Multiple(errors) => errors
+                .iter()
+                .any(|error| contains(&error.name) || contains(&error.reason)),
+        }
+}
+
+fn group(error: &Failed) -> String {
+    match &error.reason {
+        Reason::Single(reason) => reason.clone(),
+        Reason::Multiple(_) => "Some samples couldn't be ripped".to_owned(),
+    }
+}
@@ -0,0 +1,208 @@
+//! Switch between named sets of ripping and naming settings
+
+use data::config::Profile;
+use iced::widget::{column, pick_list, row, text, text_input};
+use iced::{Alignment, Command, Length};
+
+use crate::theme;
+use crate::widget::helpers::{action, control};
+use crate::widget::Element;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Handled by the application, since it owns the settings
+    Select(String),
+    /// Handled by the application, saves the current settings to the selected profile
+    Save,
+    /// Handled by the application, saves the current settings as a new profile
+    Create,
+    NameInput(String),
+    Duplicate,
+    Rename,
+    Delete,
+    /// The profile to select once the list is refreshed
+    Result(Result<Option<String>, String>),
+}
+
+#[derive(Debug, Default)]
+pub struct Profiles {
+    names: Vec<String>,
+    selected: Option<String>,
+    name_input: String,
+    error: Option<String>,
+}
+
+impl Profiles {
+    pub fn new(selected: Option<String>) -> Self {
+        Self {
+            names: Profile::list(),
+            selected,
+            ..Default::default()
+        }
+    }
+
+    /// The profile has been loaded by the application
+    pub fn set_selected(&mut self, name: String) {
+        self.error = None;
+        self.selected = Some(name);
+    }
+
+    pub fn set_error(&mut self, error: impl ToString) {
+        self.error = Some(error.to_string());
+    }
+
+    /// Overwrite the selected profile with the current settings
+    pub fn save(&mut self, profile: Profile) -> Command<Message> {
+        match self.selected.clone() {
+            Some(name) => save(name, profile),
+            None => Command::none(),
+        }
+    }
+
+    /// Save the current settings as a new profile
+    pub fn create(&mut self, profile: Profile) -> Command<Message> {
+        let Some(name) = self.new_name() else {
+            return Command::none();
+        };
+
+        if Profile::exists(&name) {
+            self.set_error(format!("A profile named \"{}\" already exists", name));
+            return Command::none();
+        }
+
+        save(name, profile)
+    }
+
+    /// Name typed by the user, if it can be used for a new profile
+    fn new_name(&self) -> Option<String> {
+        let name = self.name_input.trim();
+        Profile::is_valid_name(name).then(|| name.to_owned())
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        tracing::info!("{:?}", &message);
+
+        match message {
+            Message::NameInput(name) => self.name_input = name,
+            Message::Duplicate => {
+                if let (Some(name), Some(new_name)) = (self.selected.clone(), self.new_name()) {
+                    return Command::perform(
+                        async move {
+                            Profile::duplicate(&name, &new_name)
+                                .await
+                                .map(|_| Some(new_name))
+                        },
+                        result,
+                    );
+                }
+            }
+            Message::Rename => {
+                if let (Some(name), Some(new_name)) = (self.selected.clone(), self.new_name()) {
+                    return Command::perform(
+                        async move {
+                            Profile::rename(&name, &new_name)
+                                .await
+                                .map(|_| Some(new_name))
+                        },
+                        result,
+                    );
+                }
+            }
+            Message::Delete => {
+                if let Some(name) = self.selected.clone() {
+                    return Command::perform(
+                        async move { Profile::delete(&name).await.map(|_| None) },
+                        result,
+                    );
+                }
+            }
+            Message::Result(result) => {
+                self.names = Profile::list();
+
+                match result {
+                    Ok(selected) => {
+                        self.name_input.clear();
+                        self.error = None;
+                        self.selected = selected;
+                    }
+                    Err(error) => self.error = Some(error),
+                }
+            }
+            Message::Select(_) | Message::Save | Message::Create => (),
+        }
+
+        Command::none()
+    }
+
+    /// Compact picker shown in the main window
+    pub fn view_picker(&self) -> Element<Message> {
+        row![
+            text("Profile"),
+            self.picker(),
+            action("Save", self.selected.is_some().then_some(Message::Save)),
+        ]
+        .spacing(8)
+        .align_items(Alignment::Center)
+        .into()
+    }
+
+    pub fn view(&self) -> Element<Message> {
+        let selected = self.selected.is_some();
+        let named = self.new_name().is_some();
+
+        let picker = row![
+            self.picker(),
+            action("Delete", selected.then_some(Message::Delete)).style(theme::Button::Cancel),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center);
+
+        let manage = row![
+            text_input("Profile name", &self.name_input)
+                .on_input(Message::NameInput)
+                .width(Length::Fill),
+            action("New", named.then_some(Message::Create)),
+            action(
+                "Duplicate",
+                (selected && named).then_some(Message::Duplicate)
+            ),
+            action("Rename", (selected && named).then_some(Message::Rename)),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center);
+
+        let error = self
+            .error
+            .as_ref()
+            .map(|error| text(error).style(theme::Text::Error));
+
+        control(
+            "Profiles",
+            column![picker, manage].push_maybe(error).spacing(8),
+        )
+        .into()
+    }
+
+    fn picker(&self) -> Element<Message> {
+        pick_list(
+            self.names.as_slice(),
+            self.selected.as_ref(),
+            Message::Select,
+        )
+        .placeholder("No profile")
+        .width(Length::Fill)
+        .into()
+    }
+}
+
+fn result(result: anyhow::Result<Option<String>>) -> Message {
+    Message::Result(result.map_err(|e| e.to_string()))
+}
+
+/// Save the settings as a profile, selecting it afterwards
+fn save(name: String, profile: Profile) -> Command<Message> {
+    Command::perform(
+        async move { profile.save(&name).await.map(|_| Some(name)) },
+        result,
+    )
+}
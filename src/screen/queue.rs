@@ -0,0 +1,188 @@
+//! View and rearrange the jobs waiting to be ripped
+
+use iced::widget::{button, column, container, row, scrollable, text, Space};
+use iced::{Alignment, Length};
+
+use crate::ripper::queue::{Completed, Job, Queue};
+use crate::ripper::subscription::CompleteState;
+use crate::theme;
+use crate::utils::filename;
+use crate::widget::helpers::{control, text_adv};
+use crate::widget::Element;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Inspect(usize),
+    MoveUp(usize),
+    MoveDown(usize),
+    Remove(usize),
+    ClearCompleted,
+    /// Handled by the application
+    BrowseErrors(usize),
+}
+
+#[derive(Debug, Default)]
+pub struct QueuePanel {
+    /// The job that shows its entries and configuration
+    inspected: Option<usize>,
+}
+
+impl QueuePanel {
+    pub fn update(&mut self, queue: &mut Queue, message: Message) {
+        tracing::info!("{:?}", &message);
+
+        match message {
+            Message::Inspect(id) => {
+                self.inspected = match self.inspected == Some(id) {
+                    true => None,
+                    false => Some(id),
+                }
+            }
+            Message::MoveUp(id) => queue.move_up(id),
+            Message::MoveDown(id) => queue.move_down(id),
+            Message::Remove(id) => {
+                queue.remove(id);
+            }
+            Message::ClearCompleted => queue.clear_completed(),
+            Message::BrowseErrors(_) => (),
+        }
+    }
+
+    pub fn view<'a>(&'a self, queue: &'a Queue) -> Element<'a, Message> {
+        let current = queue
+            .current()
+            .map(|id| text(format!("Ripping job #{}", id)));
+
+        let pending: Element<Message> = match queue.is_empty() {
+            true => text("No jobs are waiting.").into(),
+            false => column(queue.pending().map(|job| self.view_job(job)))
+                .spacing(8)
+                .into(),
+        };
+
+        let completed = (!queue.completed().is_empty()).then(|| {
+            let jobs = column(queue.completed().iter().map(view_completed)).spacing(8);
+
+            let clear = button("Clear")
+                .on_press(Message::ClearCompleted)
+                .style(theme::Button::Cancel);
+
+            control(
+                row![text("Completed"), Space::with_width(Length::Fill), clear]
+                    .align_items(Alignment::Center),
+                jobs,
+            )
+        });
+
+        let content = column![]
+            .push_maybe(current)
+            .push(control(text(format!("Pending ({})", queue.len())), pending))
+            .push_maybe(completed)
+            .spacing(10);
+
+        scrollable(content).into()
+    }
+
+    fn view_job<'a>(&'a self, job: &'a Job) -> Element<'a, Message> {
+        let ripping = &job.signal.ripping;
+
+        let summary = text(format!(
+            "#{} - {} entries to {}",
+            job.id,
+            job.entries().len(),
+            ripping.exported_format
+        ));
+
+        let buttons = row![
+            button("Inspect")
+                .on_press(Message::Inspect(job.id))
+                .style(theme::Button::Hyperlink),
+            Space::with_width(Length::Fill),
+            button("Up").on_press(Message::MoveUp(job.id)),
+            button("Down").on_press(Message::MoveDown(job.id)),
+            button("Remove")
+                .on_press(Message::Remove(job.id))
+                .style(theme::Button::Cancel),
+        ]
+        .spacing(5)
+        .align_items(Alignment::Center);
+
+        let details = (self.inspected == Some(job.id)).then(|| {
+            let naming = &job.signal.naming;
+
+            let settings = column![
+                text(format!("Destination: {}", job.destination().display())),
+                text(format!(
+                    "Self Contained: {}, Strict: {}, Channels: {}",
+                    ripping.self_contained, ripping.strict, ripping.channels
+                )),
+                text(format!(
+                    "Prefix: {}, Raw Index: {}, Index Only: {}",
+                    naming.prefix, naming.index_raw, naming.index_only
+                )),
+            ]
+            .spacing(4);
+
+            let entries = column(
+                job.entries()
+                    .iter()
+                    .map(|path| text_adv(filename(path)).size(11).into()),
+            );
+
+            column![settings, entries].spacing(8)
+        });
+
+        let content = column![summary, text_adv(job.destination().display()).size(11)]
+            .push_maybe(details)
+            .push(buttons)
+            .spacing(4);
+
+        container(content)
+            .padding(6)
+            .width(Length::Fill)
+            .style(theme::Container::Black)
+            .into()
+    }
+}
+
+fn view_completed(job: &Completed) -> Element<Message> {
+    let (summary, has_errors) = match &job.state {
+        CompleteState::NoErrors => ("Done".to_owned(), false),
+        CompleteState::Cancelled => ("Cancelled".to_owned(), false),
+        CompleteState::Aborted => ("Aborted".to_owned(), false),
+        CompleteState::SomeErrors(errors) => (format!("{} error(s)", errors.len()), true),
+        CompleteState::TooMuchErrors { total, .. } => (format!("{} error(s)", total), true),
+        CompleteState::TooMuchErrorsNoLog {
+            errors, discarded, ..
+        } => (
+            format!("{} error(s)", errors.len() as u64 + discarded),
+            true,
+        ),
+    };
+
+    let browse = has_errors.then(|| {
+        button("Browse Errors")
+            .on_press(Message::BrowseErrors(job.id))
+            .style(theme::Button::Hyperlink)
+    });
+
+    let content = row![
+        column![
+            text(format!(
+                "#{} - {} entries: {}, {}",
+                job.id, job.entries, summary, job.time
+            )),
+            text_adv(job.destination.display()).size(11),
+        ]
+        .spacing(4),
+        Space::with_width(Length::Fill),
+    ]
+    .push_maybe(browse)
+    .align_items(Alignment::Center);
+
+    container(content)
+        .padding(6)
+        .width(Length::Fill)
+        .style(theme::Container::Black)
+        .into()
+}
@@ -1,11 +1,13 @@
+use data::config::general::ErrorLogFormat;
 use data::time::Time;
 use iced::Command;
 use std::path::{Path, PathBuf};
 
 use crate::app::Message;
 
-use crate::ripper::extraction::error::Reason;
+use crate::ripper::activity::Feed;
 use crate::ripper::extraction::error_handler::{self, ErrorHandler};
+use crate::ripper::progress::Stats;
 use crate::ripper::subscription::CompleteState;
 use crate::utils::create_file_dialog;
 use crate::widget::helpers::{
@@ -25,8 +27,10 @@ pub enum RippingState {
     /// The application is currently ripping samples
     Ripping {
         message: Option<String>,
-        progress: f32,
-        errors: u64,
+        stats: Stats,
+        /// Recently processed modules
+        feed: Feed,
+        paused: bool,
     },
     /// The application has finished ripping samples
     Finished {
@@ -37,13 +41,10 @@ pub enum RippingState {
 }
 
 impl RippingState {
-    pub fn update_progress(&mut self, new_progress: f32, new_errors: u64) {
-        if let Self::Ripping {
-            progress, errors, ..
-        } = self
-        {
-            *progress = new_progress;
-            *errors = new_errors;
+    pub fn update_progress(&mut self, new_stats: Stats, recent: Feed) {
+        if let Self::Ripping { stats, feed, .. } = self {
+            *stats = new_stats;
+            feed.extend(recent);
         }
     }
 
@@ -57,6 +58,16 @@ impl RippingState {
         self.update_message(Some(message.into()))
     }
 
+    pub fn set_paused(&mut self, paused: bool) {
+        if let Self::Ripping { paused: state, .. } = self {
+            *state = paused
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        matches!(self, Self::Ripping { paused: true, .. })
+    }
+
     pub fn is_ripping(&self) -> bool {
         matches!(self, Self::Ripping { .. })
     }
@@ -65,7 +76,7 @@ impl RippingState {
         matches!(self, Self::Finished { .. })
     }
 
-    pub fn export_errors(&mut self) -> Command<Message> {
+    pub fn export_errors(&mut self, format: ErrorLogFormat) -> Command<Message> {
         let RippingState::Finished { state, .. } = &self else {
             return Command::none();
         };
@@ -75,35 +86,77 @@ impl RippingState {
         };
 
         let task = async move {
-            let Some(path) = create_file_dialog(error_handler::random_name()).await else {
+            let Some(path) = create_file_dialog(error_handler::random_name(format)).await else {
                 return Err(String::new()); // todo
             };
 
-            ErrorHandler::dump(errors, path).await
+            ErrorHandler::dump(errors, path, format).await
         };
 
         Command::perform(task, Message::SaveErrorsResult)
     }
+
+    /// Collect the modules that failed so they can be ripped again.
+    ///
+    /// They're read back from the error log if they were written to one.
+    pub fn retry_failed(&self) -> Command<Message> {
+        let RippingState::Finished { state, .. } = &self else {
+            return Command::none();
+        };
+
+        match state {
+            CompleteState::TooMuchErrors { log, .. } => Command::perform(
+                ErrorHandler::read(log.to_owned()),
+                Message::RetryFailedResult,
+            ),
+            state => {
+                let errors = state.errors_ref().cloned().unwrap_or_default();
+                Command::perform(async { Ok(errors) }, Message::RetryFailedResult)
+            }
+        }
+    }
 }
 
-pub fn view_ripping(
-    message: &Option<String>,
-    progress: f32,
-    total_errors: u64,
+pub fn view_ripping<'a>(
+    message: &'a Option<String>,
+    stats: &'a Stats,
+    feed: &'a Feed,
+    paused: bool,
     show_gif: bool,
-) -> Element<Message> {
+) -> Element<'a, Message> {
     let cancel_ripping_button = button("CANCEL")
         .on_press(Message::Cancel)
         .style(theme::Button::Cancel)
         .padding(5);
 
+    let pause_button = match paused {
+        true => button("RESUME").on_press(Message::Resume),
+        false => button("PAUSE").on_press(Message::Pause),
+    }
+    .padding(5);
+
+    let message = match paused {
+        true => "Paused",
+        false => message.as_deref().unwrap_or("Ripping..."),
+    };
+
     let view = column![
-        text(message.as_deref().unwrap_or("Ripping...")),
-        text(format!("{}% - Errors: {}", progress.floor(), total_errors)),
-        progress_bar(0.0..=100.0, progress).height(5).width(200),
-        cancel_ripping_button,
+        text(message),
+        text(format!(
+            "{}% - Errors: {} - Skipped: {}",
+            stats.percentage().floor(),
+            stats.errors,
+            stats.skipped
+        )),
+        text(stats.throughput()).size(12),
+        text(stats.timing()).size(12),
+        progress_bar(0.0..=100.0, stats.percentage())
+            .height(5)
+            .width(200),
+        row![pause_button, cancel_ripping_button].spacing(8),
     ]
     .push_maybe(show_gif.then(|| widget::animation::GIF.ripping()).flatten())
+    .push_maybe((!feed.is_empty()).then(|| view_feed(feed)))
     .spacing(8)
     .align_items(Alignment::Center);
 
@@ -112,6 +165,26 @@ pub fn view_ripping(
         .into()
 }
 
+/// Modules that were recently processed, errors are highlighted.
+fn view_feed(feed: &Feed) -> Element<Message> {
+    let entries = column(feed.iter().map(|activity| {
+        let style = match activity.error {
+            Some(_) => theme::Text::Error,
+            None => theme::Text::Default,
+        };
+
+        text_adv(activity).size(11).style(style).into()
+    }))
+    .spacing(2);
+
+    container(scrollable(entries).width(Length::Fill))
+        .padding(6)
+        .width(400)
+        .height(150)
+        .style(theme::Container::Frame)
+        .into()
+}
+
 /// XMODITS has finished extracting the samples
 pub fn view_finished<'a>(
     complete_state: &'a CompleteState,
@@ -132,6 +205,14 @@ pub fn view_finished<'a>(
         .on_press(Message::Open(destination.display().to_string()))
         .padding(5);
 
+    let browse_errors_button = button("Browse Errors")
+        .on_press(Message::BrowseErrors)
+        .padding(5);
+
+    let retry_button = button("Retry Failed")
+        .on_press(Message::RetryFailed)
+        .padding(5);
+
     match complete_state {
         CompleteState::NoErrors => centered_container(
             column![
@@ -175,17 +256,20 @@ pub fn view_finished<'a>(
             ]
             .align_items(Alignment::Center);
 
-            let buttons = row![continue_button, open_destination_button, save_errors_button]
-                .padding(4)
-                .spacing(6)
-                .align_items(Alignment::Center);
+            let buttons = row![
+                continue_button,
+                open_destination_button,
+                save_errors_button,
+                browse_errors_button,
+                retry_button
+            ]
+            .padding(4)
+            .spacing(6)
+            .align_items(Alignment::Center);
 
             let errors = scrollable(
                 column(errors.iter().map(|error| {
-                    let reason = match &error.reason {
-                        Reason::Single(single) => centered_text(single),
-                        Reason::Multiple(_) => centered_text("multiple..."), //todo
-                    };
+                    let reason = centered_text(&error.reason);
 
                     let error = text_adv(error.filename());
                     let error = container(column![error, reason])
@@ -214,10 +298,15 @@ pub fn view_finished<'a>(
                     .style(theme::Button::HyperlinkInverted),
                 centered_text(format!("{} errors written.", total)),
                 centered_text(format!("{}.", time)),
-                row![continue_button, open_destination_button]
-                    .spacing(8)
-                    .padding(4)
-                    .align_items(Alignment::Center)
+                row![
+                    continue_button,
+                    open_destination_button,
+                    browse_errors_button,
+                    retry_button
+                ]
+                .spacing(8)
+                .padding(4)
+                .align_items(Alignment::Center)
             ]
             .align_items(Alignment::Center)
             .padding(4)
@@ -242,10 +331,16 @@ pub fn view_finished<'a>(
                 )),
             };
 
-            let buttons = row![continue_button, open_destination_button, save_errors_button]
-                .padding(4)
-                .spacing(8)
-                .align_items(Alignment::Center);
+            let buttons = row![
+                continue_button,
+                open_destination_button,
+                save_errors_button,
+                browse_errors_button,
+                retry_button
+            ]
+            .padding(4)
+            .spacing(8)
+            .align_items(Alignment::Center);
 
             let view = column![
                 text("Done..."),
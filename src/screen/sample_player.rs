@@ -10,6 +10,8 @@ pub use preview_manager::*;
 pub mod preview_manager_dummy {
     use crate::screen::entry::Entries;
 
+    use data::config::SampleProcessingConfig;
+
     use iced::{window::Id, Command};
     use std::path::PathBuf;
 
@@ -32,6 +34,9 @@ pub mod preview_manager_dummy {
         pub fn close_all(&self) -> Command<Message> {
             Command::none()
         }
+        pub fn set_processing(&mut self, _processing: SampleProcessingConfig) -> Command<Message> {
+            Command::none()
+        }
         pub fn remove_instance(&self, _id: Id) {}
         pub fn set_hovered(&mut self, _id: Id, _hovered: bool) {}
         pub fn close(&mut self, _id: Id) {}
@@ -3,7 +3,8 @@ mod sample;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
-use audio_engine::{PlayerHandle, TrackerSample};
+use audio_engine::{PlayerHandle, SampleBuffer, TrackerSample};
+use data::config::SampleProcessingConfig;
 use iced::widget::{button, checkbox, column, progress_bar, row, scrollable, slider, text, Space};
 use iced::{command, Alignment, Command, Length};
 
@@ -71,11 +72,16 @@ pub struct Instance {
     settings: MediaSettings,
     pub hovered: bool,
     progress: Option<f32>,
+    processing: SampleProcessingConfig,
 }
 
 impl Instance {
-    pub fn new(player: PlayerHandle, path: PathBuf) -> (Self, Command<Message>) {
-        let mut instance = Self::new_empty(player);
+    pub fn new(
+        player: PlayerHandle,
+        path: PathBuf,
+        processing: SampleProcessingConfig,
+    ) -> (Self, Command<Message>) {
+        let mut instance = Self::new_empty(player).processing(processing);
         let command = instance.load_samples(path);
 
         (instance, command)
@@ -88,6 +94,7 @@ impl Instance {
             settings: MediaSettings::default(),
             hovered: false,
             progress: None,
+            processing: SampleProcessingConfig::default(),
         }
     }
 
@@ -96,6 +103,30 @@ impl Instance {
         self
     }
 
+    pub fn processing(mut self, processing: SampleProcessingConfig) -> Self {
+        self.processing = processing;
+        self
+    }
+
+    /// Reload the samples if the processing chain has changed,
+    /// so that the user hears what will be exported.
+    pub fn set_processing(&mut self, processing: SampleProcessingConfig) -> Command<Message> {
+        if self.processing == processing {
+            return Command::none();
+        }
+
+        self.processing = processing;
+
+        match self.loaded_path().map(Path::to_owned) {
+            Some(path) => {
+                self.state = State::Loading;
+                self.player.stop();
+                load_samples(path, self.processing)
+            }
+            None => Command::none(),
+        }
+    }
+
     pub fn update(&mut self, message: Message, entries: &mut Entries) -> Command<Message> {
         match message {
             Message::Select(index) => {
@@ -184,7 +215,7 @@ impl Instance {
         });
 
         let static_noise_warning = warning(
-            || false,
+            || self.selected_is_noise(),
             "WARNING - This sample is most likely static noise.",
         );
 
@@ -218,10 +249,11 @@ impl Instance {
     }
 
     pub fn load_samples(&mut self, module_path: PathBuf) -> Command<Message> {
+        let processing = self.processing;
         let load = |state: &mut State, path: PathBuf| {
             *state = State::Loading;
             self.player.stop();
-            load_samples(path)
+            load_samples(path, processing)
         };
 
         match &self.state {
@@ -251,6 +283,16 @@ impl Instance {
         }
     }
 
+    fn selected_is_noise(&self) -> bool {
+        match &self.state {
+            State::Loaded {
+                selected: Some(index),
+                samples,
+            } => samples.is_noise(*index),
+            _ => false,
+        }
+    }
+
     pub fn play_selected(&self) -> Command<Message> {
         match &self.state {
             State::Loaded {
@@ -407,7 +449,18 @@ fn play_sample(handle: &PlayerHandle, source: TrackerSample) -> Command<Message>
     })
 }
 
-fn load_samples(path: PathBuf) -> Command<Message> {
+/// Apply the same processing chain used by the exporter
+fn process(sample: TrackerSample, processing: &SampleProcessingConfig) -> TrackerSample {
+    if !processing.is_active() {
+        return sample;
+    }
+
+    let mut buffer = SampleBuffer::clone(&sample.buf);
+    crate::dsp::process(&mut buffer.buf, buffer.rate(), processing);
+    TrackerSample::new(buffer)
+}
+
+fn load_samples(path: PathBuf, processing: SampleProcessingConfig) -> Command<Message> {
     use crate::logger::log_file_on_panic;
     use xmodits_lib::Error;
 
@@ -438,12 +491,15 @@ fn load_samples(path: PathBuf) -> Command<Message> {
                         .into_iter()
                         .map(|result| match result {
                             Ok((metadata, buffer)) => {
+                                let analysis = crate::dsp::noise::analyse(&buffer.buf.buf);
+                                let buffer = process(buffer, &processing);
                                 let peaks = buffer.buf.peaks(Duration::from_millis(5));
                                 let waveform = WaveData::from(peaks);
                                 SampleResult::Valid {
                                     metadata,
                                     buffer,
                                     waveform,
+                                    analysis,
                                 }
                             }
                             Err(error) => SampleResult::Invalid(error.to_string()),
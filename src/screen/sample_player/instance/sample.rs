@@ -2,6 +2,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use crate::dsp::noise::Analysis;
 use crate::icon;
 use crate::theme;
 use crate::widget::helpers::centered_container;
@@ -50,6 +51,11 @@ impl SamplePack {
             .and_then(SampleResult::tracker_sample)
     }
 
+    /// The sample is most likely static noise
+    pub fn is_noise(&self, index: usize) -> bool {
+        self.samples.get(index).is_some_and(SampleResult::is_noise)
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -70,6 +76,8 @@ pub enum SampleResult {
         metadata: audio_engine::Metadata,
         buffer: TrackerSample,
         waveform: WaveData,
+        /// Measured before the sample is processed
+        analysis: Analysis,
     },
 }
 
@@ -97,6 +105,10 @@ impl SampleResult {
         matches!(self, Self::Invalid(_))
     }
 
+    pub fn is_noise(&self) -> bool {
+        matches!(self, Self::Valid { analysis, .. } if analysis.is_noise())
+    }
+
     pub fn tracker_sample(&self) -> Option<TrackerSample> {
         match &self {
             SampleResult::Valid { buffer, .. } => Some(buffer.clone()),
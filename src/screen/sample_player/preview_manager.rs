@@ -11,6 +11,7 @@ use crate::screen::entry::Entries;
 use crate::widget::Element;
 
 use audio_engine::SamplePlayer;
+use data::config::SampleProcessingConfig;
 
 const WINDOW_SIZE: Size = Size::new(640.0, 500.0);
 
@@ -25,6 +26,7 @@ pub struct SamplePreview {
     windows: HashMap<Id, Instance>,
     singleton: bool,
     default_settings: MediaSettings,
+    processing: SampleProcessingConfig,
 }
 
 impl SamplePreview {
@@ -83,7 +85,8 @@ impl SamplePreview {
             ..Default::default()
         });
 
-        let (instance, load_samples) = Instance::new(self.audio_engine.create_handle(), path);
+        let (instance, load_samples) =
+            Instance::new(self.audio_engine.create_handle(), path, self.processing);
 
         self.windows
             .insert(id, instance.settings(self.default_settings));
@@ -94,6 +97,18 @@ impl SamplePreview {
         ])
     }
 
+    /// Set the processing chain used to preview samples
+    pub fn set_processing(&mut self, processing: SampleProcessingConfig) -> Command<Message> {
+        self.processing = processing;
+
+        Command::batch(self.windows.iter_mut().map(|(id, window)| {
+            let id = *id;
+            window
+                .set_processing(processing)
+                .map(move |msg| Message::Window(id, msg))
+        }))
+    }
+
     pub fn get_title(&self, id: Id) -> String {
         self.get_window(id).title()
     }
@@ -1,8 +1,11 @@
+pub mod transfer;
+
+use data::config::general::ErrorLogFormat;
 use data::config::{self};
 use iced::widget::{checkbox, column, pick_list, row};
 use iced::Command;
 
-use crate::widget::helpers::control;
+use crate::widget::helpers::{control, labelled_picklist};
 use crate::widget::Element;
 
 #[derive(Debug, Clone)]
@@ -12,6 +15,8 @@ pub enum Message {
     ShowAnimatedGIF(bool),
     SuppressWarnings(bool),
     ShowErrorsInTextEditor(bool),
+    AutoSave(bool),
+    ErrorLogFormat(ErrorLogFormat),
     SetTheme(data::theme::Themes),
 }
 
@@ -42,6 +47,15 @@ pub fn view(general: &config::GeneralConfig) -> Element<Message> {
             )
             .on_toggle(Message::ShowErrorsInTextEditor),
         )
+        .push(
+            checkbox("Save Settings Automatically", general.auto_save).on_toggle(Message::AutoSave),
+        )
+        .push(labelled_picklist(
+            "Error Log Format",
+            ErrorLogFormat::ALL,
+            Some(general.error_log_format),
+            Message::ErrorLogFormat,
+        ))
         .spacing(8);
 
     column![control("Application Settings", settings)]
@@ -99,6 +113,8 @@ pub fn update(cfg: &mut config::GeneralConfig, message: Message) -> Command<Mess
         Message::SuppressWarnings(toggle) => cfg.suppress_warnings = toggle,
         Message::SetTheme(theme) => cfg.theme = theme,
         Message::ShowErrorsInTextEditor(show) => cfg.show_errors_in_text_editor = show,
+        Message::ErrorLogFormat(format) => cfg.error_log_format = format,
+        Message::AutoSave(auto_save) => cfg.auto_save = auto_save,
     }
 
     Command::none()
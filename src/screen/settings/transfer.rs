@@ -0,0 +1,150 @@
+//! Share a standard setup by exporting the settings to a file, and importing them elsewhere.
+
+use std::path::PathBuf;
+
+use data::config::{Recovery, Sections};
+use data::Config;
+use iced::widget::{checkbox, column, row, text};
+use iced::{Alignment, Command};
+
+use crate::theme;
+use crate::utils::{create_file_dialog, settings_file_dialog};
+use crate::widget::helpers::{action, control};
+use crate::widget::Element;
+
+const EXPORT_NAME: &str = "xmodits-settings.toml";
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    /// Handled by the application, since it owns the settings
+    Export,
+    ExportResult(Option<Result<PathBuf, String>>),
+    Import,
+    /// Handled by the application, `None` if the user didn't pick a file
+    ImportResult(Option<Result<(Config, Sections, Recovery), String>>),
+    General(bool),
+    Ripping(bool),
+    Naming(bool),
+}
+
+#[derive(Debug, Default)]
+pub struct Transfer {
+    /// Sections to import
+    sections: Sections,
+    /// What happened to the last import or export
+    status: Option<Result<String, String>>,
+}
+
+impl Transfer {
+    pub fn sections(&self) -> Sections {
+        self.sections
+    }
+
+    pub fn set_status(&mut self, status: Result<String, String>) {
+        self.status = Some(status);
+    }
+
+    /// Write the settings to a file picked by the user
+    pub fn export(&self, config: Config) -> Command<Message> {
+        let task = async move {
+            let path = create_file_dialog(EXPORT_NAME.to_owned()).await?;
+
+            Some(match config.export_file(&path).await {
+                Ok(()) => Ok(path),
+                Err(e) => Err(e.to_string()),
+            })
+        };
+
+        Command::perform(task, Message::ExportResult)
+    }
+
+    pub fn update(&mut self, message: Message) -> Command<Message> {
+        tracing::info!("{:?}", &message);
+
+        match message {
+            Message::ExportResult(Some(result)) => {
+                self.status = Some(match result {
+                    Ok(path) => Ok(format!("Exported settings to: {}", path.display())),
+                    Err(e) => Err(format!("Failed to export settings: {}", e)),
+                })
+            }
+            Message::Import => {
+                let task = async {
+                    let path = settings_file_dialog().await?;
+                    Some(Config::import_file(&path).await.map_err(|e| e.to_string()))
+                };
+
+                return Command::perform(task, Message::ImportResult);
+            }
+            Message::General(general) => self.sections.general = general,
+            Message::Ripping(ripping) => self.sections.ripping = ripping,
+            Message::Naming(naming) => self.sections.naming = naming,
+            Message::Export | Message::ExportResult(None) | Message::ImportResult(_) => (),
+        }
+
+        Command::none()
+    }
+
+    pub fn view(&self) -> Element<Message> {
+        let sections = row![
+            text("Import:"),
+            checkbox("General", self.sections.general).on_toggle(Message::General),
+            checkbox("Ripping", self.sections.ripping).on_toggle(Message::Ripping),
+            checkbox("Naming", self.sections.naming).on_toggle(Message::Naming),
+        ]
+        .spacing(8)
+        .align_items(Alignment::Center);
+
+        let buttons = row![
+            action("Export Settings...", Some(Message::Export)),
+            action(
+                "Import Settings...",
+                self.sections.any().then_some(Message::Import)
+            ),
+        ]
+        .spacing(8);
+
+        let status = self.status.as_ref().map(|status| match status {
+            Ok(status) => text(status),
+            Err(error) => text(error).style(theme::Text::Error),
+        });
+
+        let content = column![sections, buttons].push_maybe(status).spacing(8);
+
+        control("Share Settings", content).into()
+    }
+}
+
+/// Describe an import that went through
+pub fn imported(sections: Sections, recovery: &Recovery) -> String {
+    let names: Vec<&str> = [
+        (sections.general, "general"),
+        (sections.ripping, "ripping"),
+        (sections.naming, "naming"),
+    ]
+    .into_iter()
+    .filter_map(|(selected, name)| selected.then_some(name))
+    .collect();
+
+    let mut status = format!(
+        "Imported {} settings. Save them to keep them.",
+        names.join(", ")
+    );
+
+    // Only mention the sections that were imported
+    let dropped: Vec<&str> = recovery
+        .dropped
+        .iter()
+        .map(String::as_str)
+        .filter(|key| names.iter().any(|name| key.starts_with(name)))
+        .collect();
+
+    if !dropped.is_empty() {
+        status.push_str(&format!(
+            " These couldn't be read and were reset to their defaults: {}",
+            dropped.join(", ")
+        ));
+    }
+
+    status
+}
@@ -31,6 +31,14 @@ pub async fn files_dialog() -> Option<Vec<PathBuf>> {
     rfd::AsyncFileDialog::new().pick_files().await.map(paths)
 }
 
+pub async fn settings_file_dialog() -> Option<PathBuf> {
+    rfd::AsyncFileDialog::new()
+        .add_filter("Settings", &["toml"])
+        .pick_file()
+        .await
+        .map(|handle| handle.path().to_owned())
+}
+
 fn paths(handles: Vec<rfd::FileHandle>) -> Vec<PathBuf> {
     handles.into_iter().map(|d| d.path().to_owned()).collect()
 }